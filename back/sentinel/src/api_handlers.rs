@@ -4,13 +4,24 @@ use tracing::{info, error};
 use chrono::Utc;
 
 use crate::models::{
-    CheckRequest, WriteRequest, WriteResponse, ReadRequest, ReadResponse,
-    RelationTuple, Operation, BatchCheckRequest
+    CheckRequest, WriteRequest, WriteResponse, ReadRequest, ReadResponse, DeleteRequest, DeleteResponse, DeleteObjectResponse, EraseUserResponse,
+    RelationTuple, Operation, BatchCheckRequest, ExpandRequest,
+    LookupResourcesRequest, LookupSubjectsRequest, WatchRequest, ReconcileRequest, UpdateNamespaceRequest
 };
+use crate::namespace_config::NamespaceConfig;
+use crate::caveat::Caveat;
+use crate::errors::SentinelError;
+use crate::cache::Cache;
 use crate::zookie::Zookie;
 use crate::permission_checker::PermissionChecker;
+use crate::expander::Expander;
+use crate::lookup::Lookup;
+use crate::watch::{ChangeWatcher, validate_start};
+use crate::precondition::check_preconditions;
+use crate::tuple_writer::{plan_updates, apply_plan, delete_matching, delete_object};
+use crate::reconciler::IndexReconciler;
+use crate::erasure;
 use crate::tuple_store::{TupleStore, ScyllaTupleStore};
-use crate::cache::{Cache, RedisCache};
 use crate::AppState;
 
 /// Zanzibar Check API - 권한 검증 (캐싱 포함)
@@ -22,8 +33,11 @@ pub async fn check_permission(
     info!("Permission check request: {}:{}#{} for user:{}", 
         req.namespace, req.object_id, req.relation, req.user_id);
 
+    // 가정한 변경도 Write와 같은 규칙으로 검증
+    data.namespaces.validate_updates(&req.hypothetical).await?;
+
     let tuple_store = Arc::new(ScyllaTupleStore::new(data.session.clone()));
-    let checker = PermissionChecker::new(tuple_store, data.cache.clone(), data.zookie_manager.clone());
+    let checker = PermissionChecker::new(tuple_store, data.namespaces.clone(), data.cache.clone(), data.zookie_manager.clone());
 
     match checker.check_permission(&req).await {
         Ok(response) => {
@@ -41,6 +55,7 @@ pub async fn check_permission(
 }
 
 /// Zanzibar Write API - 권한 튜플 생성/삭제 (캐시 무효화 포함)
+/// Create 튜플은 LWT로 선점하므로 동시에 같은 튜플을 Create하면 하나만 성공하고 나머지는 409
 /// POST /api/v1/write
 pub async fn write_permissions(
     data: web::Data<AppState>,
@@ -48,70 +63,53 @@ pub async fn write_permissions(
 ) -> Result<HttpResponse> {
     info!("Write request with {} tuple updates", req.updates.len());
 
-    let tuple_store = Arc::new(ScyllaTupleStore::new(data.session.clone()));
-    let checker = PermissionChecker::new(tuple_store.clone(), data.cache.clone(), data.zookie_manager.clone());
+    // 설정에 맞지 않는 튜플이 있으면 아무것도 저장하지 않고 거부
+    data.namespaces.validate_updates(&req.updates).await?;
 
-    let mut success_count = 0;
-    let mut errors = Vec::new();
-    let mut affected_objects = std::collections::HashSet::new();
-    let mut affected_users = std::collections::HashSet::new();
+    let tuple_store = Arc::new(ScyllaTupleStore::new(data.session.clone()));
+    let checker = PermissionChecker::new(tuple_store.clone(), data.namespaces.clone(), data.cache.clone(), data.zookie_manager.clone());
 
-    for update in &req.updates {
-        let tuple = RelationTuple {
-            namespace: update.tuple.namespace.clone(),
-            object_id: update.tuple.object_id.clone(),
-            relation: update.tuple.relation.clone(),
-            user_type: update.tuple.user_type.clone(),
-            user_id: update.tuple.user_id.clone(),
-            created_at: scylla::value::CqlTimestamp(Utc::now().timestamp_millis()),
-        };
-
-        let result = match update.operation {
-            Operation::Insert => {
-                info!("Inserting tuple: {}:{}#{}@{}:{}", 
-                    tuple.namespace, tuple.object_id, tuple.relation, tuple.user_type, tuple.user_id);
-                tuple_store.insert_tuple(&tuple).await
-            }
-            Operation::Delete => {
-                info!("Deleting tuple: {}:{}#{}@{}:{}", 
-                    tuple.namespace, tuple.object_id, tuple.relation, tuple.user_type, tuple.user_id);
-                tuple_store.delete_tuple(&tuple).await
-            }
-        };
-
-        match result {
-            Ok(_) => {
-                success_count += 1;
-                // 캐시 무효화를 위해 영향받은 객체와 사용자 추적
-                affected_objects.insert((tuple.namespace.clone(), tuple.object_id.clone()));
-                if tuple.user_type == "user" {
-                    affected_users.insert(tuple.user_id.clone());
-                }
-            }
-            Err(e) => {
-                error!("Tuple operation failed: {}", e);
-                errors.push(e.to_string());
-            }
-        }
+    // 선행 조건을 하나라도 만족하지 않으면 아무것도 저장하지 않고 거부
+    // (최선 노력 검사: 검사 뒤 반영 전에 다른 쓰기가 조건을 깨뜨리는 경우는 막지 못함)
+    if let Some(preconditions) = &req.preconditions {
+        check_preconditions(tuple_store.as_ref(), preconditions).await?;
     }
 
-    // 성공한 작업이 있으면 관련 캐시 무효화
-    if success_count > 0 {
-        // 객체별 캐시 무효화
-        for (namespace, object_id) in affected_objects {
-            if let Err(e) = checker.invalidate_object_cache(&namespace, &object_id).await {
-                error!("Failed to invalidate object cache for {}:{}: {}", namespace, object_id, e);
-            }
-        }
-        
-        // 사용자별 캐시 무효화
-        for user_id in affected_users {
-            if let Err(e) = checker.invalidate_user_cache(&user_id).await {
-                error!("Failed to invalidate user cache for {}: {}", user_id, e);
-            }
+    let updates: Vec<(Operation, RelationTuple)> = req.updates.iter()
+        .map(|update| {
+            let tuple = RelationTuple {
+                namespace: update.tuple.namespace.clone(),
+                object_id: update.tuple.object_id.clone(),
+                relation: update.tuple.relation.clone(),
+                user_type: update.tuple.user_type.clone(),
+                user_id: update.tuple.user_id.clone(),
+                created_at: scylla::value::CqlTimestamp(Utc::now().timestamp_millis()),
+                expires_at: update.tuple.expires_at
+                    .map(|expires_at| scylla::value::CqlTimestamp(expires_at.timestamp_millis())),
+                caveat: update.tuple.caveat.as_ref().map(Caveat::to_json),
+            };
+            (update.operation.clone(), tuple)
+        })
+        .collect();
+
+    // 현재 상태와 비교해 실제 변경만 추림 (이미 있는 튜플을 Create하면 409로 전체 거부)
+    let plan = plan_updates(tuple_store.as_ref(), &updates).await?;
+
+    // 모든 변경을 한 번에 반영 (실패하면 아무것도 반영되지 않고 zookie도 발급하지 않음)
+    // 그 사이 다른 쓰기가 Create할 튜플을 먼저 만들었으면 409로 전체 거부
+    if let Err(e) = apply_plan(tuple_store.as_ref(), &plan).await {
+        if matches!(e, SentinelError::ConflictError { .. }) {
+            return Err(e.into());
         }
+        error!("Write request failed, no updates applied: {}", e);
+        return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": "Write failed",
+            "message": e.to_string()
+        })));
     }
 
+    invalidate_tuple_caches(&checker, plan.mutations.iter().map(|(_, tuple)| tuple)).await;
+
     // 새로운 쓰기 Zookie 생성
     let write_zookie = data.zookie_manager.generate_zookie().await.map_err(|e| {
         error!("Failed to generate write zookie: {}", e);
@@ -120,23 +118,191 @@ pub async fn write_permissions(
     
     let response = WriteResponse {
         zookie: write_zookie.to_string().unwrap_or_else(|_| format!("{}", Utc::now().timestamp_millis())),
+        results: plan.results,
+    };
+
+    info!("Write request completed: {} of {} updates changed tuples", plan.mutations.len(), updates.len());
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// 변경된 튜플의 객체별, 사용자별 권한 체크 캐시 무효화
+async fn invalidate_tuple_caches<'a, C: Cache>(
+    checker: &PermissionChecker<C>,
+    tuples: impl IntoIterator<Item = &'a RelationTuple>,
+) {
+    // 캐시 무효화를 위해 영향받은 객체와 사용자 추적
+    let mut affected_objects = std::collections::HashSet::new();
+    let mut affected_users = std::collections::HashSet::new();
+    let mut relinked_objects = std::collections::HashSet::new();
+    for tuple in tuples {
+        affected_objects.insert((tuple.namespace.clone(), tuple.object_id.clone()));
+        // user:* 튜플은 패턴 check:*@user:*로 모든 사용자의 캐시를 무효화
+        if tuple.user_type == "user" {
+            affected_users.insert(tuple.user_id.clone());
+        } else {
+            relinked_objects.insert((tuple.namespace.clone(), tuple.object_id.clone()));
+        }
+    }
+
+    // userset이나 부모 객체 연결이 바뀌면 객체의 관계를 상속받는 객체의 캐시도 무효화
+    // (예: folders:x#viewer@userset:teams:backend#member -> folders:x를 parent로 가진 문서)
+    for (namespace, object_id) in relinked_objects {
+        match checker.dependent_objects(&namespace, &object_id).await {
+            Ok(dependents) => affected_objects.extend(dependents),
+            Err(e) => error!("Failed to find objects depending on {}:{}: {}", namespace, object_id, e),
+        }
+    }
+
+    // 객체별 캐시 무효화
+    for (namespace, object_id) in affected_objects {
+        if let Err(e) = checker.invalidate_object_cache(&namespace, &object_id).await {
+            error!("Failed to invalidate object cache for {}:{}: {}", namespace, object_id, e);
+        }
+    }
+    
+    // 사용자별 캐시 무효화
+    for user_id in affected_users {
+        if let Err(e) = checker.invalidate_user_cache(&user_id).await {
+            error!("Failed to invalidate user cache for {}: {}", user_id, e);
+        }
+    }
+}
+
+/// 필터에 맞는 권한 튜플 삭제 (DeleteRelationships)
+/// POST /api/v1/delete
+pub async fn delete_relationships(
+    data: web::Data<AppState>,
+    req: web::Json<DeleteRequest>,
+) -> Result<HttpResponse> {
+    info!("Delete request for filter: {:?} (limit: {:?})", req.tuple_filter, req.limit);
+
+    let tuple_store = Arc::new(ScyllaTupleStore::new(data.session.clone()));
+    let checker = PermissionChecker::new(tuple_store.clone(), data.namespaces.clone(), data.cache.clone(), data.zookie_manager.clone());
+
+    let outcome = match delete_matching(tuple_store.as_ref(), &req.tuple_filter, req.limit.map(|limit| limit as usize)).await {
+        Ok(outcome) => outcome,
+        Err(e @ SentinelError::ValidationError { .. }) => return Err(e.into()),
+        Err(e) => {
+            error!("Delete request failed: {}", e);
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Delete failed",
+                "message": e.to_string()
+            })));
+        }
+    };
+
+    invalidate_tuple_caches(&checker, &outcome.deleted).await;
+
+    let delete_zookie = data.zookie_manager.generate_zookie().await?;
+    let response = DeleteResponse {
+        deleted_count: outcome.deleted.len() as u64,
+        complete: outcome.complete,
+        zookie: delete_zookie.to_string()?,
+    };
+
+    info!("Delete request completed: {} tuples deleted (complete: {})", response.deleted_count, response.complete);
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// 객체 삭제 API - 객체가 리소스이거나 주체인 모든 튜플 삭제
+/// DELETE /api/v1/objects/{namespace}/{object_id}
+pub async fn delete_object_relationships(
+    data: web::Data<AppState>,
+    path: web::Path<(String, String)>,
+) -> Result<HttpResponse> {
+    let (namespace, object_id) = path.into_inner();
+    info!("Delete object request: {}:{}", namespace, object_id);
+
+    let tuple_store = Arc::new(ScyllaTupleStore::new(data.session.clone()));
+    let checker = PermissionChecker::new(tuple_store.clone(), data.namespaces.clone(), data.cache.clone(), data.zookie_manager.clone());
+
+    let deletion = match delete_object(tuple_store.as_ref(), &data.namespaces, &namespace, &object_id).await {
+        Ok(deletion) => deletion,
+        Err(e) => {
+            error!("Delete object request failed: {}", e);
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Delete failed",
+                "message": e.to_string()
+            })));
+        }
+    };
+
+    invalidate_tuple_caches(&checker, deletion.resource_tuples.iter().chain(&deletion.subject_tuples)).await;
+
+    let delete_zookie = data.zookie_manager.generate_zookie().await?;
+    let response = DeleteObjectResponse {
+        namespace,
+        object_id,
+        resource_tuples: deletion.resource_tuples.len() as u64,
+        subject_tuples: deletion.subject_tuples.len() as u64,
+        zookie: delete_zookie.to_string()?,
+    };
+
+    info!(
+        "Delete object request completed: {} resource tuples and {} subject tuples deleted",
+        response.resource_tuples, response.subject_tuples
+    );
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// 사용자 삭제 API - 사용자의 모든 튜플 삭제, 변경 이력 가명 처리, 캐시 무효화 후 영수증 반환
+/// POST /api/v1/users/{user_id}/erase
+pub async fn erase_user(
+    data: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<HttpResponse> {
+    let user_id = path.into_inner();
+    info!("Erase user request");
+
+    let tuple_store = ScyllaTupleStore::new(data.session.clone());
+
+    let receipt = match erasure::erase_user(&tuple_store, &data.namespaces, data.cache.as_ref(), &user_id).await {
+        Ok(receipt) => receipt,
+        Err(e @ SentinelError::ValidationError { .. }) => return Err(e.into()),
+        Err(e) => {
+            error!("Erase user request failed: {}", e);
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Erasure failed",
+                "message": e.to_string()
+            })));
+        }
     };
 
-    if errors.is_empty() {
-        info!("Write request completed: {} operations successful", success_count);
-        Ok(HttpResponse::Ok().json(response))
-    } else {
-        error!("Write request partially failed: {} errors", errors.len());
-        Ok(HttpResponse::BadRequest().json(serde_json::json!({
-            "error": "Some operations failed",
-            "successful_count": success_count,
-            "errors": errors,
-            "response": response
-        })))
+    let erase_zookie = data.zookie_manager.generate_zookie().await?;
+    Ok(HttpResponse::Ok().json(EraseUserResponse {
+        receipt,
+        zookie: erase_zookie.to_string()?,
+    }))
+}
+
+/// 사용자 삭제 영수증 조회
+/// GET /api/v1/erasures/{receipt_id}
+pub async fn get_erasure(
+    data: web::Data<AppState>,
+    path: web::Path<uuid::Uuid>,
+) -> Result<HttpResponse> {
+    let receipt_id = path.into_inner();
+    let tuple_store = ScyllaTupleStore::new(data.session.clone());
+
+    match tuple_store.find_erasure(receipt_id).await {
+        Ok(Some(receipt)) => Ok(HttpResponse::Ok().json(receipt)),
+        Ok(None) => Err(SentinelError::not_found_error(format!("Erasure receipt {} not found", receipt_id)).into()),
+        Err(e) => {
+            error!("Failed to get erasure receipt: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to get erasure receipt",
+                "message": e.to_string()
+            })))
+        }
     }
 }
 
-/// Zanzibar Read API - 권한 튜플 조회
+/// Read API 기본 페이지 크기
+const DEFAULT_READ_PAGE_SIZE: u32 = 100;
+/// Read API 최대 페이지 크기
+const MAX_READ_PAGE_SIZE: u32 = 1000;
+
+/// Zanzibar Read API - 권한 튜플 조회 (페이지 단위)
 /// POST /api/v1/read
 pub async fn read_permissions(
     data: web::Data<AppState>,
@@ -146,45 +312,32 @@ pub async fn read_permissions(
 
     let tuple_store = Arc::new(ScyllaTupleStore::new(data.session.clone()));
 
-    // 필터에 따른 조회 로직
-    let tuples_result = if let (Some(namespace), Some(object_id)) = (
-        &req.tuple_filter.namespace,
-        &req.tuple_filter.object_id,
-    ) {
-        if let Some(relation) = &req.tuple_filter.relation {
-            // 특정 객체-관계에 대한 튜플 조회
-            tuple_store.find_tuples_by_object_relation(namespace, object_id, relation).await
-        } else {
-            // 특정 객체에 대한 모든 튜플 조회
-            tuple_store.find_tuples_by_object(namespace, object_id).await
-        }
-    } else if let Some(user_id) = &req.tuple_filter.user_id {
-        // 특정 사용자의 모든 권한 조회
-        tuple_store.find_user_memberships(user_id).await
-    } else {
-        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
-            "error": "Invalid filter",
-            "message": "Must specify either (namespace, object_id) or user_id"
-        })));
-    };
+    let page_size = req.page_size.unwrap_or(DEFAULT_READ_PAGE_SIZE).clamp(1, MAX_READ_PAGE_SIZE);
+    // 다른 필터로 만들어졌거나 변조된 토큰은 400으로 거부
+    let paging_state = req.page_token.as_deref()
+        .map(|token| data.page_tokens.decode(token, &req.tuple_filter))
+        .transpose()?;
 
-    match tuples_result {
-        Ok(tuples) => {
-            info!("Read request completed: {} tuples found", tuples.len());
+    match tuple_store.read_tuples(&req.tuple_filter, page_size, paging_state).await {
+        Ok(page) => {
+            info!("Read request completed: {} tuples found", page.tuples.len());
             
-            let api_tuples = tuples.iter().map(|t| t.to_api_tuple()).collect::<Vec<_>>();
+            let api_tuples = page.tuples.iter().map(|t| t.to_api_tuple()).collect::<Vec<_>>();
+            let next_page_token = page.paging_state
+                .map(|state| data.page_tokens.encode(&req.tuple_filter, &state));
             
             // 읽기 Zookie 생성
             let read_zookie = data.zookie_manager.generate_zookie().await.unwrap_or_else(|_| Zookie::new());
             
             let response = ReadResponse {
                 tuples: api_tuples,
-                next_page_token: None, // TODO: 페이징 구현
+                next_page_token,
                 zookie: read_zookie.to_string().unwrap_or_else(|_| format!("{}", Utc::now().timestamp_millis())),
             };
             
             Ok(HttpResponse::Ok().json(response))
         }
+        Err(e @ SentinelError::ValidationError { .. }) => Err(e.into()),
         Err(e) => {
             error!("Read request failed: {}", e);
             Ok(HttpResponse::InternalServerError().json(serde_json::json!({
@@ -205,7 +358,7 @@ pub async fn get_user_permissions(
     info!("Getting permissions for user: {}", user_id);
 
     let tuple_store = Arc::new(ScyllaTupleStore::new(data.session.clone()));
-    let checker = PermissionChecker::new(tuple_store, data.cache.clone(), data.zookie_manager.clone());
+    let checker = PermissionChecker::new(tuple_store, data.namespaces.clone(), data.cache.clone(), data.zookie_manager.clone());
 
     match checker.get_user_permissions(&user_id).await {
         Ok(permissions) => {
@@ -239,7 +392,7 @@ pub async fn get_object_permissions(
     info!("Getting permissions for object: {}:{}", namespace, object_id);
 
     let tuple_store = Arc::new(ScyllaTupleStore::new(data.session.clone()));
-    let checker = PermissionChecker::new(tuple_store, data.cache.clone(), data.zookie_manager.clone());
+    let checker = PermissionChecker::new(tuple_store, data.namespaces.clone(), data.cache.clone(), data.zookie_manager.clone());
 
     match checker.get_object_permissions(&namespace, &object_id).await {
         Ok(permissions) => {
@@ -272,8 +425,13 @@ pub async fn batch_check_permissions(
 ) -> Result<HttpResponse> {
     info!("Batch permission check request with {} items", req.checks.len());
 
+    data.namespaces.validate_updates(&req.hypothetical).await?;
+    for check in &req.checks {
+        data.namespaces.validate_updates(&check.hypothetical).await?;
+    }
+
     let tuple_store = Arc::new(ScyllaTupleStore::new(data.session.clone()));
-    let checker = PermissionChecker::new(tuple_store, data.cache.clone(), data.zookie_manager.clone());
+    let checker = PermissionChecker::new(tuple_store, data.namespaces.clone(), data.cache.clone(), data.zookie_manager.clone());
 
     match checker.batch_check_permissions(&req).await {
         Ok(response) => {
@@ -292,4 +450,216 @@ pub async fn batch_check_permissions(
             })))
         }
     }
-}
\ No newline at end of file
+}
+
+/// Zanzibar Expand API - 객체-관계의 userset 트리 조회
+/// POST /api/v1/expand
+pub async fn expand(
+    data: web::Data<AppState>,
+    req: web::Json<ExpandRequest>,
+) -> Result<HttpResponse> {
+    info!("Expand request: {}:{}#{}", req.namespace, req.object_id, req.relation);
+
+    let tuple_store = Arc::new(ScyllaTupleStore::new(data.session.clone()));
+    let expander = Expander::new(tuple_store, data.namespaces.clone(), data.zookie_manager.clone());
+
+    match expander.expand(&req).await {
+        Ok(response) => Ok(HttpResponse::Ok().json(response)),
+        Err(e) => {
+            error!("Expand failed: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Expand failed",
+                "message": e.to_string()
+            })))
+        }
+    }
+}
+
+/// LookupResources API - 사용자가 관계를 가진 모든 객체 조회
+/// POST /api/v1/lookup_resources
+pub async fn lookup_resources(
+    data: web::Data<AppState>,
+    req: web::Json<LookupResourcesRequest>,
+) -> Result<HttpResponse> {
+    info!("Lookup resources request: {}#{} for user:{}", req.namespace, req.relation, req.user_id);
+
+    let tuple_store = Arc::new(ScyllaTupleStore::new(data.session.clone()));
+    let lookup = Lookup::new(tuple_store, data.namespaces.clone(), data.cache.clone(), data.zookie_manager.clone());
+
+    match lookup.lookup_resources(&req).await {
+        Ok(response) => Ok(HttpResponse::Ok().json(response)),
+        Err(e) => {
+            error!("Lookup resources failed: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Lookup resources failed",
+                "message": e.to_string()
+            })))
+        }
+    }
+}
+
+/// LookupSubjects API - 객체-관계를 가진 모든 사용자 조회 (페이지 단위)
+/// POST /api/v1/lookup_subjects
+pub async fn lookup_subjects(
+    data: web::Data<AppState>,
+    req: web::Json<LookupSubjectsRequest>,
+) -> Result<HttpResponse> {
+    info!("Lookup subjects request: {}:{}#{}", req.namespace, req.object_id, req.relation);
+
+    let tuple_store = Arc::new(ScyllaTupleStore::new(data.session.clone()));
+    let lookup = Lookup::new(tuple_store, data.namespaces.clone(), data.cache.clone(), data.zookie_manager.clone());
+
+    match lookup.lookup_subjects(&req).await {
+        Ok(response) => Ok(HttpResponse::Ok().json(response)),
+        Err(e @ SentinelError::ValidationError { .. }) => Err(e.into()),
+        Err(e) => {
+            error!("Lookup subjects failed: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Lookup subjects failed",
+                "message": e.to_string()
+            })))
+        }
+    }
+}
+
+/// Watch API - 네임스페이스의 튜플 변경을 server-sent events로 전송
+/// GET /api/v1/watch?namespace=documents&zookie=...
+pub async fn watch(
+    data: web::Data<AppState>,
+    query: web::Query<WatchRequest>,
+) -> Result<HttpResponse> {
+    let start = match query.zookie.as_deref() {
+        Some(zookie) => Zookie::from_string(zookie)?,
+        None => data.zookie_manager.generate_zookie().await?,
+    };
+    info!("Watch request: {} from {}", query.namespace, start.timestamp_micros);
+    validate_start(&start, Utc::now().timestamp_micros())?;
+
+    let tuple_store = Arc::new(ScyllaTupleStore::new(data.session.clone()));
+    let watcher = ChangeWatcher::new(tuple_store, query.namespace.clone(), &start);
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header(("Cache-Control", "no-cache"))
+        .streaming(watcher.into_sse_stream()))
+}
+
+/// 인덱스 테이블 정합성 검사 및 복구
+/// POST /api/v1/reconcile?dry_run=true
+pub async fn reconcile_indexes(
+    data: web::Data<AppState>,
+    query: web::Query<ReconcileRequest>,
+) -> Result<HttpResponse> {
+    let dry_run = query.dry_run.unwrap_or(false);
+    info!("Index reconcile request (dry run: {})", dry_run);
+
+    let reconciler = IndexReconciler::new(Arc::new(ScyllaTupleStore::new(data.session.clone())));
+    let report = reconciler.reconcile(dry_run).await?;
+    Ok(HttpResponse::Ok().json(report))
+}
+
+/// 네임스페이스 목록 조회
+/// GET /api/v1/namespaces
+pub async fn list_namespaces(data: web::Data<AppState>) -> Result<HttpResponse> {
+    let mut namespaces = data.namespaces.list_records().await?;
+    namespaces.sort_by(|a, b| a.config.name.cmp(&b.config.name));
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "count": namespaces.len(),
+        "namespaces": namespaces
+    })))
+}
+
+/// 네임스페이스 등록
+/// POST /api/v1/namespaces
+pub async fn create_namespace(
+    data: web::Data<AppState>,
+    req: web::Json<NamespaceConfig>,
+) -> Result<HttpResponse> {
+    info!("Create namespace request: {}", req.name);
+
+    let tuple_store = Arc::new(ScyllaTupleStore::new(data.session.clone()));
+    let record = data.namespaces.create(req.into_inner(), tuple_store.as_ref()).await?;
+    // 설정 변경은 이미 반영되었으므로 무효화 실패는 기록만 함 (invalidate_namespace_cache)
+    let checker = PermissionChecker::new(tuple_store, data.namespaces.clone(), data.cache.clone(), data.zookie_manager.clone());
+    let _ = checker.invalidate_namespace_cache(&record.config.name).await;
+
+    Ok(HttpResponse::Created().json(record))
+}
+
+/// 네임스페이스 설정 조회
+/// GET /api/v1/namespaces/{name}
+pub async fn get_namespace(
+    data: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<HttpResponse> {
+    let name = path.into_inner();
+
+    match data.namespaces.find_record(&name).await? {
+        Some(record) => Ok(HttpResponse::Ok().json(record)),
+        None => Err(SentinelError::not_found_error(format!("Namespace '{}' is not registered", name)).into()),
+    }
+}
+
+/// 특정 버전의 네임스페이스 설정 조회
+/// GET /api/v1/namespaces/{name}/versions/{version}
+pub async fn get_namespace_version(
+    data: web::Data<AppState>,
+    path: web::Path<(String, i32)>,
+) -> Result<HttpResponse> {
+    let (name, version) = path.into_inner();
+
+    match data.namespaces.find_record_version(&name, version).await? {
+        Some(record) => Ok(HttpResponse::Ok().json(record)),
+        None => Err(SentinelError::not_found_error(format!(
+            "Namespace '{}' has no version {}", name, version
+        )).into()),
+    }
+}
+
+/// 네임스페이스 설정 수정 (새 버전 생성)
+/// PUT /api/v1/namespaces/{name}
+pub async fn update_namespace(
+    data: web::Data<AppState>,
+    path: web::Path<String>,
+    req: web::Json<UpdateNamespaceRequest>,
+) -> Result<HttpResponse> {
+    let name = path.into_inner();
+    info!("Update namespace request: {} (expected version: {:?})", name, req.expected_version);
+
+    let req = req.into_inner();
+    if req.config.name != name {
+        return Err(SentinelError::validation_error(format!(
+            "Config name '{}' does not match namespace '{}'", req.config.name, name
+        )).into());
+    }
+
+    let tuple_store = Arc::new(ScyllaTupleStore::new(data.session.clone()));
+    let record = data.namespaces.update(req.config, req.expected_version, tuple_store.as_ref()).await?;
+    // 설정 변경은 이미 반영되었으므로 무효화 실패는 기록만 함 (invalidate_namespace_cache)
+    let checker = PermissionChecker::new(tuple_store, data.namespaces.clone(), data.cache.clone(), data.zookie_manager.clone());
+    let _ = checker.invalidate_namespace_cache(&name).await;
+
+    Ok(HttpResponse::Ok().json(record))
+}
+
+/// 네임스페이스 삭제 (튜플이 남아 있으면 거부)
+/// DELETE /api/v1/namespaces/{name}
+pub async fn delete_namespace(
+    data: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<HttpResponse> {
+    let name = path.into_inner();
+    info!("Delete namespace request: {}", name);
+
+    let tuple_store = Arc::new(ScyllaTupleStore::new(data.session.clone()));
+    data.namespaces.delete(&name, tuple_store.as_ref()).await?;
+    // 설정 변경은 이미 반영되었으므로 무효화 실패는 기록만 함 (invalidate_namespace_cache)
+    let checker = PermissionChecker::new(tuple_store, data.namespaces.clone(), data.cache.clone(), data.zookie_manager.clone());
+    let _ = checker.invalidate_namespace_cache(&name).await;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "name": name,
+        "deleted": true
+    })))
+}
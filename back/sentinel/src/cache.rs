@@ -3,7 +3,7 @@ use redis::{Client as RedisClient, AsyncCommands};
 use serde::{Deserialize, Serialize};
 use tracing::{info, warn, error};
 use crate::errors::{SentinelError, SentinelResult};
-use crate::models::{CheckRequest, CheckResponse};
+use crate::models::{CheckRequest, CheckResponse, Permissionship};
 
 /// 캐시 추상화 trait
 /// 권한 체크 결과와 관련 메타데이터를 캐싱
@@ -192,8 +192,12 @@ impl CachedCheckResult {
     pub fn to_check_response(&self, current_zookie: &str) -> CheckResponse {
         CheckResponse {
             allowed: self.allowed,
+            // 조건부 튜플로 계산한 결과는 캐시하지 않으므로 허용 또는 거부
+            permissionship: Permissionship::from_allowed(self.allowed),
+            missing_context: Vec::new(),
             // 현재 요청의 zookie 사용 (일관성 보장)
             zookie: current_zookie.to_string(),
+            explanation: None,
         }
     }
     
@@ -242,9 +246,17 @@ impl CacheKeyBuilder {
     
     /// 네임스페이스의 모든 권한 캐시 무효화를 위한 패턴
     /// 형식: "check:{namespace}:*"
+    #[allow(dead_code)]
     pub fn namespace_permission_pattern(namespace: &str) -> String {
         format!("check:{}:*", namespace)
     }
+
+    /// 모든 권한 캐시 무효화를 위한 패턴 (네임스페이스 설정 변경 시)
+    /// userset과 tuple_to_userset으로 다른 네임스페이스의 설정을 거쳐 계산한 결과도 있으므로 전체 무효화
+    /// 형식: "check:*"
+    pub fn all_permissions_pattern() -> &'static str {
+        "check:*"
+    }
 }
 
 /// 캐시 TTL 상수
@@ -255,9 +267,11 @@ impl CacheTTL {
     pub const PERMISSION_CHECK: u64 = 5 * 60; // 300초
     
     /// 사용자 권한 목록 캐시 TTL (10분)
+    #[allow(dead_code)]
     pub const USER_PERMISSIONS: u64 = 10 * 60; // 600초
     
     /// 객체 권한 목록 캐시 TTL (10분)
+    #[allow(dead_code)]
     pub const OBJECT_PERMISSIONS: u64 = 10 * 60; // 600초
 }
 
@@ -274,6 +288,9 @@ mod tests {
             user_id: "alice".to_string(),
             user_type: Some("user".to_string()),
             zookie: None,
+            context: Default::default(),
+            explain: false,
+            hypothetical: Vec::new(),
         };
         
         let key = CacheKeyBuilder::check_permission_key(&request);
@@ -290,7 +307,10 @@ mod tests {
     fn test_cached_check_result_serialization() {
         let response = CheckResponse {
             allowed: true,
+            permissionship: Permissionship::Allowed,
+            missing_context: Vec::new(),
             zookie: "1234567890".to_string(),
+            explanation: None,
         };
         
         let cached = CachedCheckResult::from_check_response(&response);
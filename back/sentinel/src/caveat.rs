@@ -0,0 +1,301 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::net::IpAddr;
+use chrono::{DateTime, Datelike, Duration, NaiveTime, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::errors::{SentinelError, SentinelResult};
+
+/// Check 요청의 context (조건 평가에 사용하는 값, 예: {"ip": "10.0.0.5"})
+pub type CheckContext = BTreeMap<String, Value>;
+
+/// 튜플에 붙는 조건 (caveat)
+/// 조건이 있는 튜플은 Check 시점에 조건을 만족할 때만 권한을 부여
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Caveat {
+    /// context[field]의 IP 주소가 CIDR 중 하나에 속함 (예: 사무실 네트워크)
+    IpInCidr {
+        field: String,
+        cidrs: Vec<String>,
+    },
+    /// 서버 시간이 start ~ end(HH:MM, utc_offset_minutes 시간대) 사이이고 weekdays 중 하나 (예: 업무 시간)
+    /// end가 start보다 이르면 자정을 넘는 구간, weekdays가 비어 있으면 모든 요일
+    TimeWindow {
+        start: String,
+        end: String,
+        #[serde(default)]
+        weekdays: Vec<Weekday>,
+        #[serde(default)]
+        utc_offset_minutes: i32,
+    },
+    /// context[field]와 value 비교 (예: department == "sales")
+    Attribute {
+        field: String,
+        op: CompareOp,
+        value: Value,
+    },
+    /// 하위 조건을 모두 만족
+    AllOf(Vec<Caveat>),
+}
+
+/// Attribute 조건의 비교 연산
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    /// value 배열에 포함
+    In,
+}
+
+/// 조건 평가 결과
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CaveatOutcome {
+    Satisfied,
+    Unsatisfied,
+    /// 평가에 필요한 context 필드가 없음
+    MissingContext(BTreeSet<String>),
+}
+
+impl Caveat {
+    /// 저장된 JSON에서 조건 해석
+    pub fn from_json(json: &str) -> SentinelResult<Self> {
+        serde_json::from_str(json)
+            .map_err(|e| SentinelError::internal_error(format!("Failed to deserialize caveat: {}", e)))
+    }
+
+    /// 저장용 JSON 문자열 (문자열 키와 JSON 값만 있으므로 직렬화는 실패하지 않음)
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// 조건 형식 검증 (CIDR, 시간 형식, 비교 값)
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            Caveat::IpInCidr { field, cidrs } => {
+                require_field(field)?;
+                if cidrs.is_empty() {
+                    return Err("ip_in_cidr requires at least one CIDR".to_string());
+                }
+                match cidrs.iter().find(|cidr| parse_cidr(cidr).is_none()) {
+                    Some(invalid) => Err(format!("Invalid CIDR '{}'", invalid)),
+                    None => Ok(()),
+                }
+            }
+            Caveat::TimeWindow { start, end, utc_offset_minutes, .. } => {
+                for time in [start, end] {
+                    parse_time(time).ok_or_else(|| format!("Invalid time '{}', expected HH:MM", time))?;
+                }
+                if utc_offset_minutes.abs() >= 24 * 60 {
+                    return Err(format!("Invalid utc_offset_minutes {}", utc_offset_minutes));
+                }
+                Ok(())
+            }
+            Caveat::Attribute { field, op, value } => {
+                require_field(field)?;
+                match (op, value) {
+                    (CompareOp::In, Value::Array(_)) => Ok(()),
+                    (CompareOp::In, _) => Err("Operator 'in' requires an array value".to_string()),
+                    (CompareOp::Lt | CompareOp::Le | CompareOp::Gt | CompareOp::Ge, Value::Number(_) | Value::String(_)) => Ok(()),
+                    (CompareOp::Lt | CompareOp::Le | CompareOp::Gt | CompareOp::Ge, _) => {
+                        Err("Ordering operators require a number or string value".to_string())
+                    }
+                    _ => Ok(()),
+                }
+            }
+            Caveat::AllOf(caveats) => {
+                if caveats.is_empty() {
+                    return Err("all_of requires at least one caveat".to_string());
+                }
+                caveats.iter().try_for_each(Caveat::validate)
+            }
+        }
+    }
+
+    /// now 시점에 context로 조건 평가
+    /// 값의 형식이 잘못되었으면 (예: IP가 아닌 문자열) 만족하지 않은 것으로 처리
+    pub fn evaluate(&self, context: &CheckContext, now: DateTime<Utc>) -> CaveatOutcome {
+        let satisfied = |condition: bool| {
+            if condition { CaveatOutcome::Satisfied } else { CaveatOutcome::Unsatisfied }
+        };
+        let missing = |field: &str| CaveatOutcome::MissingContext(BTreeSet::from([field.to_string()]));
+
+        match self {
+            Caveat::IpInCidr { field, cidrs } => {
+                let Some(value) = context.get(field) else {
+                    return missing(field);
+                };
+                let ip = value.as_str().and_then(|ip| ip.parse::<IpAddr>().ok());
+                satisfied(ip.is_some_and(|ip| {
+                    cidrs.iter().filter_map(|cidr| parse_cidr(cidr)).any(|(network, prefix)| cidr_contains(network, prefix, ip))
+                }))
+            }
+            Caveat::TimeWindow { start, end, weekdays, utc_offset_minutes } => {
+                let (Some(start), Some(end)) = (parse_time(start), parse_time(end)) else {
+                    return CaveatOutcome::Unsatisfied;
+                };
+                let local = now.naive_utc() + Duration::minutes(*utc_offset_minutes as i64);
+                let time = local.time();
+                let in_window = if start <= end {
+                    start <= time && time < end
+                } else {
+                    time >= start || time < end
+                };
+                // 자정을 넘는 구간의 자정 이후는 전날 요일로 판단
+                let day = if start > end && time < end { local.weekday().pred() } else { local.weekday() };
+                satisfied(in_window && (weekdays.is_empty() || weekdays.contains(&day)))
+            }
+            Caveat::Attribute { field, op, value } => match context.get(field) {
+                Some(actual) => satisfied(compare(actual, *op, value)),
+                None => missing(field),
+            },
+            Caveat::AllOf(caveats) => {
+                let mut missing_fields = BTreeSet::new();
+                for caveat in caveats {
+                    match caveat.evaluate(context, now) {
+                        CaveatOutcome::Satisfied => {}
+                        CaveatOutcome::Unsatisfied => return CaveatOutcome::Unsatisfied,
+                        CaveatOutcome::MissingContext(fields) => missing_fields.extend(fields),
+                    }
+                }
+                if missing_fields.is_empty() {
+                    CaveatOutcome::Satisfied
+                } else {
+                    CaveatOutcome::MissingContext(missing_fields)
+                }
+            }
+        }
+    }
+}
+
+fn require_field(field: &str) -> Result<(), String> {
+    if field.is_empty() {
+        Err("Caveat field must not be empty".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+fn parse_time(time: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(time, "%H:%M").ok()
+}
+
+/// "10.0.0.0/8" -> (10.0.0.0, 8), 접두사 길이가 없으면 단일 주소
+fn parse_cidr(cidr: &str) -> Option<(IpAddr, u32)> {
+    let (address, prefix) = match cidr.split_once('/') {
+        Some((address, prefix)) => (address.parse::<IpAddr>().ok()?, Some(prefix.parse::<u32>().ok()?)),
+        None => (cidr.parse::<IpAddr>().ok()?, None),
+    };
+    let max_prefix = if address.is_ipv4() { 32 } else { 128 };
+    let prefix = prefix.unwrap_or(max_prefix);
+    (prefix <= max_prefix).then_some((address, prefix))
+}
+
+fn cidr_contains(network: IpAddr, prefix: u32, ip: IpAddr) -> bool {
+    match (network, ip) {
+        (IpAddr::V4(network), IpAddr::V4(ip)) => {
+            let mask = u32::MAX.checked_shl(32 - prefix).unwrap_or(0);
+            u32::from(network) & mask == u32::from(ip) & mask
+        }
+        (IpAddr::V6(network), IpAddr::V6(ip)) => {
+            let mask = u128::MAX.checked_shl(128 - prefix).unwrap_or(0);
+            u128::from(network) & mask == u128::from(ip) & mask
+        }
+        _ => false,
+    }
+}
+
+/// 숫자는 숫자끼리, 문자열은 문자열끼리 순서 비교 (타입이 다르면 만족하지 않음)
+fn compare(actual: &Value, op: CompareOp, expected: &Value) -> bool {
+    let ordering = match (actual, expected) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64().zip(b.as_f64()).and_then(|(a, b)| a.partial_cmp(&b)),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        _ => None,
+    };
+    match op {
+        CompareOp::Eq => actual == expected,
+        CompareOp::Ne => actual != expected,
+        CompareOp::Lt => ordering.is_some_and(|o| o.is_lt()),
+        CompareOp::Le => ordering.is_some_and(|o| o.is_le()),
+        CompareOp::Gt => ordering.is_some_and(|o| o.is_gt()),
+        CompareOp::Ge => ordering.is_some_and(|o| o.is_ge()),
+        CompareOp::In => expected.as_array().is_some_and(|values| values.contains(actual)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn caveat(value: Value) -> Caveat {
+        let caveat: Caveat = serde_json::from_value(value).unwrap();
+        caveat.validate().unwrap();
+        caveat
+    }
+
+    fn context(value: Value) -> CheckContext {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_evaluate_caveats() {
+        let now = DateTime::parse_from_rfc3339("2026-10-16T01:30:00Z").unwrap().with_timezone(&Utc);
+
+        // 사무실 네트워크
+        let office = caveat(json!({"ip_in_cidr": {"field": "ip", "cidrs": ["10.0.0.0/8", "2001:db8::/32"]}}));
+        assert_eq!(office.evaluate(&context(json!({"ip": "10.1.2.3"})), now), CaveatOutcome::Satisfied);
+        assert_eq!(office.evaluate(&context(json!({"ip": "2001:db8::1"})), now), CaveatOutcome::Satisfied);
+        assert_eq!(office.evaluate(&context(json!({"ip": "192.168.0.1"})), now), CaveatOutcome::Unsatisfied);
+        assert_eq!(office.evaluate(&context(json!({"ip": "not-an-ip"})), now), CaveatOutcome::Unsatisfied);
+        assert_eq!(
+            office.evaluate(&CheckContext::new(), now),
+            CaveatOutcome::MissingContext(BTreeSet::from(["ip".to_string()])),
+        );
+
+        // 업무 시간 (KST 평일 09:00 ~ 18:00): 2026-10-16 10:30 KST 금요일
+        let business_hours = caveat(json!({"time_window": {
+            "start": "09:00", "end": "18:00", "weekdays": ["Mon", "Tue", "Wed", "Thu", "Fri"], "utc_offset_minutes": 540
+        }}));
+        assert_eq!(business_hours.evaluate(&CheckContext::new(), now), CaveatOutcome::Satisfied);
+        assert_eq!(business_hours.evaluate(&CheckContext::new(), now + Duration::hours(9)), CaveatOutcome::Unsatisfied);
+        assert_eq!(business_hours.evaluate(&CheckContext::new(), now + Duration::days(1)), CaveatOutcome::Unsatisfied);
+        // 자정을 넘는 구간
+        let night = caveat(json!({"time_window": {"start": "22:00", "end": "02:00"}}));
+        assert_eq!(night.evaluate(&CheckContext::new(), now), CaveatOutcome::Satisfied);
+
+        // 속성 비교와 all_of
+        let sales = caveat(json!({"all_of": [
+            {"attribute": {"field": "department", "op": "in", "value": ["sales", "marketing"]}},
+            {"attribute": {"field": "level", "op": "ge", "value": 3}}
+        ]}));
+        assert_eq!(sales.evaluate(&context(json!({"department": "sales", "level": 4})), now), CaveatOutcome::Satisfied);
+        assert_eq!(sales.evaluate(&context(json!({"department": "sales", "level": 2})), now), CaveatOutcome::Unsatisfied);
+        assert_eq!(
+            sales.evaluate(&CheckContext::new(), now),
+            CaveatOutcome::MissingContext(BTreeSet::from(["department".to_string(), "level".to_string()])),
+        );
+
+        assert_eq!(Caveat::from_json(&sales.to_json()).unwrap(), sales);
+    }
+
+    #[test]
+    fn test_validate_caveats() {
+        let invalid = [
+            json!({"ip_in_cidr": {"field": "ip", "cidrs": ["10.0.0.0/33"]}}),
+            json!({"ip_in_cidr": {"field": "ip", "cidrs": []}}),
+            json!({"time_window": {"start": "9am", "end": "18:00"}}),
+            json!({"attribute": {"field": "level", "op": "in", "value": 3}}),
+            json!({"attribute": {"field": "", "op": "eq", "value": 3}}),
+            json!({"all_of": []}),
+        ];
+        for value in invalid {
+            let caveat: Caveat = serde_json::from_value(value.clone()).unwrap();
+            assert!(caveat.validate().is_err(), "{} should be rejected", value);
+        }
+    }
+}
@@ -2,6 +2,7 @@ use scylla::client::session::Session;
 use scylla::response::query_result::QueryResult;
 use redis::Client as RedisClient;
 use tracing::info;
+use crate::models::CHANGELOG_RETENTION_MICROS;
 
 /// Initialize database schema for Sentinel
 pub async fn init_schema(session: &Session) -> Result<(), Box<dyn std::error::Error>> {
@@ -36,6 +37,8 @@ pub async fn init_schema(session: &Session) -> Result<(), Box<dyn std::error::Er
     ";
 
     session.query_unpaged(create_tuples_table, &[]).await?;
+    add_column_if_missing(session, "relation_tuples", "expires_at", "timestamp").await?;
+    add_column_if_missing(session, "relation_tuples", "caveat", "text").await?;
     info!("Table 'relation_tuples' created successfully");
 
     // Create namespaces table for storing namespace configurations
@@ -49,25 +52,67 @@ pub async fn init_schema(session: &Session) -> Result<(), Box<dyn std::error::Er
     ";
 
     session.query_unpaged(create_namespaces_table, &[]).await?;
+    add_column_if_missing(session, "namespaces", "version", "int").await?;
     info!("Table 'namespaces' created successfully");
 
+    // Create namespace_versions table for keeping every namespace config version
+    let create_namespace_versions_table = "
+        CREATE TABLE IF NOT EXISTS namespace_versions (
+            name text,
+            version int,
+            config text,
+            created_at timestamp,
+            PRIMARY KEY (name, version)
+        ) WITH CLUSTERING ORDER BY (version DESC)
+    ";
+
+    session.query_unpaged(create_namespace_versions_table, &[]).await?;
+    info!("Table 'namespace_versions' created successfully");
+
     // Create changelog table for tracking changes
+    // 네임스페이스와 시간 구간(bucket)으로 파티션, 파티션 안에서는 revision 순으로 정렬
+    // (기존 changelog 테이블은 id로 파티션되어 시간/네임스페이스 범위 조회가 불가능해 대체됨)
     let create_changelog_table = "
-        CREATE TABLE IF NOT EXISTS changelog (
-            id uuid,
+        CREATE TABLE IF NOT EXISTS changelog_by_namespace (
             namespace text,
+            bucket bigint,
+            revision bigint,
+            id uuid,
             object_id text,
             relation text,
             user_type text,
             user_id text,
             operation text,
             timestamp timestamp,
-            PRIMARY KEY (id, timestamp)
-        ) WITH CLUSTERING ORDER BY (timestamp DESC)
+            PRIMARY KEY ((namespace, bucket), revision, id)
+        ) WITH CLUSTERING ORDER BY (revision ASC, id ASC)
     ";
 
     session.query_unpaged(create_changelog_table, &[]).await?;
-    info!("Table 'changelog' created successfully");
+    // 보관 기간이 지난 변경 이력은 TTL로 삭제 (이전 버전에서 만든 테이블에도 적용)
+    let changelog_ttl = format!(
+        "ALTER TABLE changelog_by_namespace WITH default_time_to_live = {}",
+        CHANGELOG_RETENTION_MICROS / 1_000_000
+    );
+    session.query_unpaged(changelog_ttl, &[]).await?;
+    info!("Table 'changelog_by_namespace' created successfully");
+
+    // Create tuple_claims table for Create claims
+    // 같은 튜플을 동시에 Create하는 쓰기 중 하나만 반영되도록 튜플 키마다 선점 (행은 TTL로 삭제)
+    let create_tuple_claims_table = "
+        CREATE TABLE IF NOT EXISTS tuple_claims (
+            namespace text,
+            object_id text,
+            relation text,
+            user_type text,
+            user_id text,
+            claim_id uuid,
+            PRIMARY KEY ((namespace, object_id, relation, user_type, user_id))
+        )
+    ";
+
+    session.query_unpaged(create_tuple_claims_table, &[]).await?;
+    info!("Table 'tuple_claims' created successfully");
 
     // Create user_memberships table for fast user-based queries
     let create_user_memberships_table = "
@@ -83,6 +128,8 @@ pub async fn init_schema(session: &Session) -> Result<(), Box<dyn std::error::Er
     ";
 
     session.query_unpaged(create_user_memberships_table, &[]).await?;
+    add_column_if_missing(session, "user_memberships", "expires_at", "timestamp").await?;
+    add_column_if_missing(session, "user_memberships", "caveat", "text").await?;
     info!("Table 'user_memberships' created successfully");
 
     // Create object_permissions table for fast object-based queries  
@@ -99,6 +146,8 @@ pub async fn init_schema(session: &Session) -> Result<(), Box<dyn std::error::Er
     ";
 
     session.query_unpaged(create_object_permissions_table, &[]).await?;
+    add_column_if_missing(session, "object_permissions", "expires_at", "timestamp").await?;
+    add_column_if_missing(session, "object_permissions", "caveat", "text").await?;
     info!("Table 'object_permissions' created successfully");
 
     // Create relation_index table for fast relation-based queries
@@ -115,12 +164,46 @@ pub async fn init_schema(session: &Session) -> Result<(), Box<dyn std::error::Er
     ";
 
     session.query_unpaged(create_relation_index_table, &[]).await?;
+    add_column_if_missing(session, "relation_index", "expires_at", "timestamp").await?;
+    add_column_if_missing(session, "relation_index", "caveat", "text").await?;
     info!("Table 'relation_index' created successfully");
 
+    // Create erasure_receipts table for auditing user erasure
+    let create_erasure_receipts_table = "
+        CREATE TABLE IF NOT EXISTS erasure_receipts (
+            receipt_id uuid PRIMARY KEY,
+            receipt text,
+            erased_at timestamp
+        )
+    ";
+
+    session.query_unpaged(create_erasure_receipts_table, &[]).await?;
+    info!("Table 'erasure_receipts' created successfully");
+
     info!("Database schema initialization completed");
     Ok(())
 }
 
+/// Add a column to an existing table (tables created by older versions lack it)
+async fn add_column_if_missing(
+    session: &Session,
+    table: &str,
+    column: &str,
+    column_type: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let alter = format!("ALTER TABLE {} ADD {} {}", table, column, column_type);
+
+    match session.query_unpaged(alter, &[]).await {
+        Ok(_) => {
+            info!("Column '{}.{}' added", table, column);
+            Ok(())
+        }
+        Err(e) if e.to_string().contains("conflicts with an existing column")
+            || e.to_string().contains("already exists") => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
+
 /// Initialize Redis connection
 pub async fn init_redis(redis_host: &str, redis_port: u16) -> Result<RedisClient, Box<dyn std::error::Error>> {
     info!("Connecting to Redis at {}:{}", redis_host, redis_port);
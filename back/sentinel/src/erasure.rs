@@ -0,0 +1,122 @@
+use std::collections::BTreeSet;
+use chrono::Utc;
+use tracing::{info, warn};
+use uuid::Uuid;
+use crate::cache::{Cache, CacheKeyBuilder};
+use crate::models::{ErasureReceipt, RelationTupleFilter, WILDCARD_SUBJECT_ID};
+use crate::namespace_store::NamespaceRegistry;
+use crate::tuple_store::TupleStore;
+use crate::tuple_writer::delete_matching;
+use crate::errors::{SentinelError, SentinelResult};
+
+/// 사용자 삭제 (잊혀질 권리)
+/// 1. user_memberships 파티션으로 사용자가 주체인 모든 튜플 삭제 (삭제 이력은 Watch로 전달됨)
+/// 2. 변경 이력의 user_id를 영수증마다 새로 만든 pseudonym으로 교체 (1에서 기록된 이력 포함)
+///    등록된 네임스페이스와 1에서 튜플을 삭제한 네임스페이스의 보관 기간 안 이력만 조회
+///    (튜플이 남지 않은 미등록 네임스페이스의 이력은 changelog TTL로만 삭제됨)
+/// 3. check:*@user:{user_id} 캐시 무효화
+/// 4. 영수증 저장
+///
+/// 중간에 실패하면 다시 호출해 이어서 삭제할 수 있지만, 이미 교체된 이력은 새 영수증에 집계되지 않음
+pub async fn erase_user<C: Cache>(
+    tuple_store: &dyn TupleStore,
+    namespaces: &NamespaceRegistry,
+    cache: &C,
+    user_id: &str,
+) -> SentinelResult<ErasureReceipt> {
+    // 와일드카드는 사용자가 아니며 모든 사용자의 캐시 패턴과 겹침
+    if user_id == WILDCARD_SUBJECT_ID {
+        return Err(SentinelError::validation_error("Wildcard subject cannot be erased"));
+    }
+
+    let receipt_id = Uuid::new_v4();
+    let pseudonym = format!("erased:{}", receipt_id);
+
+    let filter = RelationTupleFilter {
+        namespace: None,
+        object_id: None,
+        relation: None,
+        user_type: Some("user".to_string()),
+        user_id: Some(user_id.to_string()),
+    };
+    let deleted = delete_matching(tuple_store, &filter, None).await?.deleted;
+    let deleted_namespaces: BTreeSet<String> = deleted.iter().map(|tuple| tuple.namespace.clone()).collect();
+
+    let mut changelog_namespaces: BTreeSet<String> = namespaces.list_records().await?
+        .into_iter()
+        .map(|record| record.config.name)
+        .collect();
+    changelog_namespaces.extend(deleted_namespaces.iter().cloned());
+    let changelog_namespaces: Vec<String> = changelog_namespaces.into_iter().collect();
+    let pseudonymized_changes = tuple_store
+        .pseudonymize_changes(&changelog_namespaces, "user", user_id, &pseudonym)
+        .await?;
+
+    let cache_invalidated = match cache.delete_pattern(&CacheKeyBuilder::user_permission_pattern(user_id)).await {
+        Ok(()) => true,
+        Err(e) => {
+            warn!("Failed to invalidate cache for erased user (receipt {}): {}", receipt_id, e);
+            false
+        }
+    };
+
+    let receipt = ErasureReceipt {
+        receipt_id,
+        pseudonym,
+        erased_at: Utc::now(),
+        deleted_tuples: deleted.len() as u64,
+        namespaces: deleted_namespaces.into_iter().collect(),
+        pseudonymized_changes,
+        cache_invalidated,
+    };
+    tuple_store.record_erasure(&receipt).await?;
+
+    info!(
+        "Erased user (receipt {}): {} tuples deleted, {} changes pseudonymized",
+        receipt.receipt_id, receipt.deleted_tuples, receipt.pseudonymized_changes
+    );
+    Ok(receipt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Operation, RelationTuple};
+    use std::sync::Arc;
+    use crate::models::ChangelogEntry;
+    use crate::test_utils::{parse_tuple, MemoryTupleStore, MemoryNamespaceStore, MockCache};
+
+    #[tokio::test]
+    async fn test_erase_user() {
+        let store = MemoryTupleStore::new();
+        let updates: Vec<(Operation, RelationTuple)> = [
+            "teams:backend#member@user:alice",
+            "documents:doc1#viewer@user:alice",
+            "documents:doc1#viewer@user:bob",
+        ].iter().map(|t| (Operation::Insert, parse_tuple(t))).collect();
+        store.write_tuples(&updates).await.unwrap();
+        // 튜플이 남지 않은 등록된 네임스페이스의 이력도 교체
+        let removed = parse_tuple("folders:shared#viewer@user:alice");
+        store.record_change(&ChangelogEntry::new(&removed, &Operation::Insert)).await.unwrap();
+        store.record_change(&ChangelogEntry::new(&removed, &Operation::Delete)).await.unwrap();
+        let namespaces = NamespaceRegistry::new(Arc::new(MemoryNamespaceStore::from_json(&[
+            r#"{"name": "folders", "relations": [{"name": "viewer"}]}"#,
+        ])));
+
+        let receipt = erase_user(&store, &namespaces, &MockCache::new(), "alice").await.unwrap();
+        assert_eq!(receipt.deleted_tuples, 2);
+        assert_eq!(receipt.namespaces, vec!["documents", "teams"]);
+        // 추가 이력 2개와 삭제 이력 2개, folders의 이력 2개
+        assert_eq!(receipt.pseudonymized_changes, 6);
+        assert!(receipt.cache_invalidated);
+
+        // 변경 이력에 원래 user_id가 남지 않음
+        let changes = store.read_changes("documents", 0, 10).await.unwrap();
+        assert!(changes.iter().all(|c| c.user_id != "alice"));
+        assert_eq!(changes.iter().filter(|c| c.user_id == receipt.pseudonym).count(), 2);
+        assert_eq!(changes.iter().filter(|c| c.user_id == "bob").count(), 1);
+
+        let stored = store.find_erasure(receipt.receipt_id).await.unwrap().unwrap();
+        assert_eq!(stored.deleted_tuples, 2);
+    }
+}
@@ -1,9 +1,11 @@
 use std::fmt;
 use std::error::Error as StdError;
 use actix_web::{HttpResponse, ResponseError};
+use serde::Serialize;
 
 /// Sentinel 시스템의 주요 에러 타입들
 #[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
 pub enum SentinelError {
     /// 데이터베이스 관련 에러 (ScyllaDB, Redis)
     DatabaseError {
@@ -13,11 +15,27 @@ pub enum SentinelError {
     /// 권한 튜플 검증 에러
     ValidationError {
         message: String,
+        /// 항목별 검증 실패 상세 (없으면 빈 목록)
+        violations: Vec<Violation>,
     },
     /// 권한 체크 관련 에러
     PermissionError {
         message: String,
     },
+    /// 요청한 리소스가 없는 경우 (예: 등록되지 않은 네임스페이스)
+    NotFoundError {
+        message: String,
+    },
+    /// 현재 상태와 충돌하는 요청 (예: 버전 불일치, 이미 존재하는 네임스페이스)
+    ConflictError {
+        message: String,
+    },
+    /// 쓰기 요청의 선행 조건 불만족
+    PreconditionFailedError {
+        message: String,
+        /// 만족하지 않은 preconditions 배열의 위치
+        index: usize,
+    },
     /// 직렬화/역직렬화 에러
     SerializationError {
         message: String,
@@ -40,12 +58,21 @@ impl fmt::Display for SentinelError {
             SentinelError::DatabaseError { message, .. } => {
                 write!(f, "database error: {}", message)
             }
-            SentinelError::ValidationError { message } => {
+            SentinelError::ValidationError { message, .. } => {
                 write!(f, "validation error: {}", message)
             }
             SentinelError::PermissionError { message } => {
                 write!(f, "permission error: {}", message)
             }
+            SentinelError::NotFoundError { message } => {
+                write!(f, "not found: {}", message)
+            }
+            SentinelError::ConflictError { message } => {
+                write!(f, "conflict: {}", message)
+            }
+            SentinelError::PreconditionFailedError { message, index } => {
+                write!(f, "precondition {} failed: {}", index, message)
+            }
             SentinelError::SerializationError { message, .. } => {
                 write!(f, "serialization error: {}", message)
             }
@@ -105,6 +132,15 @@ impl SentinelError {
     pub fn validation_error(message: impl Into<String>) -> Self {
         SentinelError::ValidationError {
             message: message.into(),
+            violations: Vec::new(),
+        }
+    }
+
+    /// 항목별 검증 실패 상세를 포함한 검증 에러 생성
+    pub fn validation_errors(message: impl Into<String>, violations: Vec<Violation>) -> Self {
+        SentinelError::ValidationError {
+            message: message.into(),
+            violations,
         }
     }
 
@@ -115,6 +151,28 @@ impl SentinelError {
         }
     }
 
+    /// Not found 에러 생성
+    pub fn not_found_error(message: impl Into<String>) -> Self {
+        SentinelError::NotFoundError {
+            message: message.into(),
+        }
+    }
+
+    /// 충돌 에러 생성
+    pub fn conflict_error(message: impl Into<String>) -> Self {
+        SentinelError::ConflictError {
+            message: message.into(),
+        }
+    }
+
+    /// 선행 조건 불만족 에러 생성
+    pub fn precondition_failed_error(index: usize, message: impl Into<String>) -> Self {
+        SentinelError::PreconditionFailedError {
+            message: message.into(),
+            index,
+        }
+    }
+
     /// 내부 에러 생성
     pub fn internal_error(message: impl Into<String>) -> Self {
         SentinelError::InternalError {
@@ -123,24 +181,72 @@ impl SentinelError {
     }
 }
 
+/// 검증 실패 상세 (요청의 몇 번째 항목의 어떤 필드가 왜 거부되었는지)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Violation {
+    /// 요청 내 항목 인덱스 (예: updates 배열의 위치)
+    pub index: usize,
+    /// 거부된 필드 (namespace, relation, user_type, user_id 등)
+    pub field: String,
+    /// 거부 사유
+    pub message: String,
+}
+
+impl Violation {
+    /// 새로운 Violation 생성
+    pub fn new(index: usize, field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            index,
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
 /// Sentinel 결과 타입 별칭
 pub type SentinelResult<T> = Result<T, SentinelError>;
 
 impl ResponseError for SentinelError {
     fn error_response(&self) -> HttpResponse {
         match self {
-            SentinelError::ValidationError { message } => {
+            SentinelError::ValidationError { message, violations } if violations.is_empty() => {
                 HttpResponse::BadRequest().json(serde_json::json!({
                     "error": "Validation error",
                     "message": message
                 }))
             }
+            SentinelError::ValidationError { message, violations } => {
+                HttpResponse::BadRequest().json(serde_json::json!({
+                    "error": "Validation error",
+                    "message": message,
+                    "violations": violations
+                }))
+            }
             SentinelError::PermissionError { message } => {
                 HttpResponse::Forbidden().json(serde_json::json!({
                     "error": "Permission error",
                     "message": message
                 }))
             }
+            SentinelError::NotFoundError { message } => {
+                HttpResponse::NotFound().json(serde_json::json!({
+                    "error": "Not found",
+                    "message": message
+                }))
+            }
+            SentinelError::ConflictError { message } => {
+                HttpResponse::Conflict().json(serde_json::json!({
+                    "error": "Conflict",
+                    "message": message
+                }))
+            }
+            SentinelError::PreconditionFailedError { message, index } => {
+                HttpResponse::PreconditionFailed().json(serde_json::json!({
+                    "error": "Precondition failed",
+                    "message": message,
+                    "index": index
+                }))
+            }
             SentinelError::DatabaseError { message, .. } => {
                 HttpResponse::InternalServerError().json(serde_json::json!({
                     "error": "Database error",
@@ -0,0 +1,306 @@
+use std::sync::Arc;
+use std::collections::HashSet;
+use async_recursion::async_recursion;
+use tracing::info;
+use crate::models::{ExpandRequest, ExpandResponse, ExpandNode, ExpandOperation};
+use crate::tuple_store::TupleStore;
+use crate::namespace_config::{NamespaceSchema, UsersetRewrite};
+use crate::namespace_store::NamespaceRegistry;
+use crate::cache::Cache;
+use crate::zookie::ZookieManager;
+use crate::errors::SentinelResult;
+
+/// 기본 최대 확장 깊이
+const DEFAULT_MAX_DEPTH: u32 = 10;
+/// 요청에서 지정할 수 있는 최대 확장 깊이
+const MAX_DEPTH_LIMIT: u32 = 50;
+
+/// Zanzibar Expand 엔진
+/// namespace:object_id#relation의 userset rewrite 규칙과 권한 계층을 따라
+/// 직접 주체와 userset 주체를 재귀적으로 펼친 트리를 생성
+pub struct Expander<C: Cache> {
+    tuple_store: Arc<dyn TupleStore>,
+    namespaces: Arc<NamespaceRegistry>,
+    zookie_manager: Arc<ZookieManager<C>>,
+}
+
+impl<C: Cache> Expander<C> {
+    /// 새로운 Expander 생성
+    pub fn new(
+        tuple_store: Arc<dyn TupleStore>,
+        namespaces: Arc<NamespaceRegistry>,
+        zookie_manager: Arc<ZookieManager<C>>,
+    ) -> Self {
+        Self {
+            tuple_store,
+            namespaces,
+            zookie_manager,
+        }
+    }
+
+    /// userset 트리 조회
+    pub async fn expand(&self, request: &ExpandRequest) -> SentinelResult<ExpandResponse> {
+        let snapshot_zookie = self.zookie_manager
+            .validate_and_get_snapshot_time(request.zookie.as_deref())
+            .await?;
+
+        let max_depth = request.max_depth.unwrap_or(DEFAULT_MAX_DEPTH).min(MAX_DEPTH_LIMIT);
+        let tree = self.expand_relation(
+            &request.namespace,
+            &request.object_id,
+            &request.relation,
+            max_depth,
+            &mut HashSet::new(),
+        ).await?;
+
+        info!("Expanded {}:{}#{} (max depth {})", request.namespace, request.object_id, request.relation, max_depth);
+        Ok(ExpandResponse {
+            tree,
+            zookie: snapshot_zookie.to_string()?,
+        })
+    }
+
+    /// 관계 확장: rewrite 규칙 트리 + 권한 계층의 상위 관계
+    /// 깊이 제한에 도달하면 truncated 노드, 현재 경로에 이미 있는 userset은 빈 노드 반환
+    #[async_recursion]
+    async fn expand_relation(
+        &self,
+        namespace: &str,
+        object_id: &str,
+        relation: &str,
+        depth: u32,
+        path: &mut HashSet<String>,
+    ) -> SentinelResult<ExpandNode> {
+        let userset = format!("{}:{}#{}", namespace, object_id, relation);
+        if depth == 0 {
+            let mut node = ExpandNode::new(userset, ExpandOperation::This);
+            node.truncated = true;
+            return Ok(node);
+        }
+        // 순환 참조: 상위 노드에서 이미 펼쳐지므로 더 추가할 주체가 없음
+        if !path.insert(userset.clone()) {
+            return Ok(ExpandNode::new(userset, ExpandOperation::This));
+        }
+
+        let schema = self.namespaces.get(namespace).await?;
+        let result = self.expand_schema_relation(&schema, object_id, relation, &userset, depth, path).await;
+
+        path.remove(&userset);
+        result
+    }
+
+    async fn expand_schema_relation(
+        &self,
+        schema: &NamespaceSchema,
+        object_id: &str,
+        relation: &str,
+        userset: &str,
+        depth: u32,
+        path: &mut HashSet<String>,
+    ) -> SentinelResult<ExpandNode> {
+        // 최상위 exclusion은 상위 관계로 상속된 주체에도 적용 (Check와 같은 의미)
+        let (granting, subtract) = match schema.config.rewrite_for(relation) {
+            UsersetRewrite::Exclusion { base, subtract } => (base.as_ref(), Some(subtract.as_ref())),
+            rewrite => (rewrite, None),
+        };
+        let mut node = self.expand_rewrite(schema, object_id, relation, userset, granting, depth, path).await?;
+
+        // 권한 계층: 바로 위 단계의 관계를 가진 주체도 포함
+        if let Some(higher_permission) = schema.hierarchy.next_higher(relation) {
+            let inherited = self.expand_relation(schema.name(), object_id, higher_permission, depth - 1, path).await?;
+            node = ExpandNode::with_children(userset, ExpandOperation::Union, vec![node, inherited]);
+        }
+
+        match subtract {
+            Some(subtract) => {
+                let subtract = self.expand_rewrite(schema, object_id, relation, userset, subtract, depth, path).await?;
+                Ok(ExpandNode::with_children(userset, ExpandOperation::Exclusion, vec![node, subtract]))
+            }
+            None => Ok(node),
+        }
+    }
+
+    /// userset rewrite 규칙 확장
+    #[allow(clippy::too_many_arguments)]
+    #[async_recursion]
+    async fn expand_rewrite(
+        &self,
+        schema: &NamespaceSchema,
+        object_id: &str,
+        relation: &str,
+        userset: &str,
+        rewrite: &UsersetRewrite,
+        depth: u32,
+        path: &mut HashSet<String>,
+    ) -> SentinelResult<ExpandNode> {
+        let namespace = schema.name();
+        match rewrite {
+            UsersetRewrite::This => {
+                let mut node = ExpandNode::new(userset, ExpandOperation::This);
+                let tuples = self.tuple_store.find_userset_members(namespace, object_id, relation).await?;
+                for tuple in tuples {
+                    match tuple.userset_reference() {
+                        Some((userset_namespace, userset_object, userset_relation)) => {
+                            let child = self.expand_relation(
+                                userset_namespace,
+                                userset_object,
+                                userset_relation,
+                                depth - 1,
+                                path,
+                            ).await?;
+                            node.children.push(child);
+                        }
+                        None => node.subjects.push(format!("{}:{}", tuple.user_type, tuple.user_id)),
+                    }
+                }
+                Ok(node)
+            }
+            UsersetRewrite::ComputedUserset { relation: computed } => {
+                let child = self.expand_relation(namespace, object_id, computed, depth - 1, path).await?;
+                Ok(ExpandNode::with_children(userset, ExpandOperation::ComputedUserset, vec![child]))
+            }
+            UsersetRewrite::TupleToUserset { tupleset, computed_userset } => {
+                let mut children = Vec::new();
+                let tuples = self.tuple_store.find_tuples_by_object_relation(namespace, object_id, tupleset).await?;
+                for tuple in tuples {
+                    let Some((parent_namespace, parent_object)) = tuple.subject_object() else {
+                        continue;
+                    };
+                    children.push(
+                        self.expand_relation(parent_namespace, parent_object, computed_userset, depth - 1, path).await?,
+                    );
+                }
+                Ok(ExpandNode::with_children(userset, ExpandOperation::TupleToUserset, children))
+            }
+            UsersetRewrite::Union(rewrites) | UsersetRewrite::Intersection(rewrites) => {
+                let operation = match rewrite {
+                    UsersetRewrite::Union(_) => ExpandOperation::Union,
+                    _ => ExpandOperation::Intersection,
+                };
+                let mut children = Vec::with_capacity(rewrites.len());
+                for child in rewrites {
+                    children.push(self.expand_rewrite(schema, object_id, relation, userset, child, depth, path).await?);
+                }
+                Ok(ExpandNode::with_children(userset, operation, children))
+            }
+            UsersetRewrite::Exclusion { base, subtract } => {
+                let base = self.expand_rewrite(schema, object_id, relation, userset, base, depth, path).await?;
+                let subtract = self.expand_rewrite(schema, object_id, relation, userset, subtract, depth, path).await?;
+                Ok(ExpandNode::with_children(userset, ExpandOperation::Exclusion, vec![base, subtract]))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{MemoryTupleStore, MockCache, MemoryNamespaceStore};
+
+    const DOCUMENTS: &str = r#"{
+        "name": "documents",
+        "relations": [
+            {"name": "owner"},
+            {"name": "parent"},
+            {"name": "viewer", "rewrite": {"union": [
+                "this",
+                {"computed_userset": {"relation": "owner"}},
+                {"tuple_to_userset": {"tupleset": "parent", "computed_userset": "viewer"}}
+            ]}}
+        ]
+    }"#;
+
+    const FOLDERS: &str = r#"{"name": "folders", "relations": [{"name": "viewer"}]}"#;
+
+    const TEAMS: &str = r#"{"name": "teams", "hierarchy": ["owner", "member"]}"#;
+
+    fn expander(tuples: &[&str]) -> Expander<MockCache> {
+        let namespaces = MemoryNamespaceStore::from_json(&[DOCUMENTS, FOLDERS, TEAMS]);
+        Expander::new(
+            Arc::new(MemoryTupleStore::with_tuples(tuples)),
+            Arc::new(NamespaceRegistry::new(Arc::new(namespaces))),
+            Arc::new(ZookieManager::new(Arc::new(MockCache::new()), None)),
+        )
+    }
+
+    fn request(relation: &str, max_depth: Option<u32>) -> ExpandRequest {
+        ExpandRequest {
+            namespace: "documents".to_string(),
+            object_id: "doc1".to_string(),
+            relation: relation.to_string(),
+            max_depth,
+            zookie: None,
+        }
+    }
+
+    /// 트리에 포함된 모든 직접 주체
+    fn subjects(node: &ExpandNode) -> Vec<String> {
+        let mut found = node.subjects.clone();
+        for child in &node.children {
+            found.extend(subjects(child));
+        }
+        found.sort();
+        found
+    }
+
+    #[tokio::test]
+    async fn test_expand_tree() {
+        let expander = expander(&[
+            "documents:doc1#owner@user:alice",
+            "documents:doc1#viewer@userset:teams:backend#member",
+            "documents:doc1#parent@folders:shared",
+            "teams:backend#owner@user:bob",
+            "teams:backend#member@user:carol",
+            "folders:shared#viewer@user:dave",
+        ]);
+
+        let tree = expander.expand(&request("viewer", None)).await.unwrap().tree;
+        assert_eq!(tree.userset, "documents:doc1#viewer");
+        assert_eq!(tree.operation, ExpandOperation::Union);
+        let operations: Vec<ExpandOperation> = tree.children.iter().map(|c| c.operation).collect();
+        assert_eq!(operations, vec![
+            ExpandOperation::This,
+            ExpandOperation::ComputedUserset,
+            ExpandOperation::TupleToUserset,
+        ]);
+        // userset 주체(teams:backend#member)는 팀 계층(owner)까지 펼쳐짐
+        assert_eq!(subjects(&tree), vec!["user:alice", "user:bob", "user:carol", "user:dave"]);
+
+        // 깊이 제한
+        let tree = expander.expand(&request("viewer", Some(1))).await.unwrap().tree;
+        assert_eq!(subjects(&tree), Vec::<String>::new());
+        assert!(tree.children[0].children[0].truncated);
+    }
+
+    #[tokio::test]
+    async fn test_expand_wildcard() {
+        let expander = expander(&[
+            "documents:doc1#viewer@user:*",
+            "documents:doc1#parent@folders:shared",
+            "folders:shared#viewer@user:*",
+        ]);
+
+        // 와일드카드는 주체 그대로 포함되며 tuple_to_userset의 부모 객체로 취급하지 않음
+        let tree = expander.expand(&request("viewer", None)).await.unwrap().tree;
+        assert_eq!(subjects(&tree), vec!["user:*", "user:*"]);
+    }
+
+    #[tokio::test]
+    async fn test_expand_cycle() {
+        let expander = expander(&[
+            "teams:a#member@userset:teams:b#member",
+            "teams:b#member@userset:teams:a#member",
+            "teams:b#member@user:alice",
+        ]);
+        let request = ExpandRequest {
+            namespace: "teams".to_string(),
+            object_id: "a".to_string(),
+            relation: "member".to_string(),
+            max_depth: None,
+            zookie: None,
+        };
+
+        let tree = expander.expand(&request).await.unwrap().tree;
+        assert_eq!(subjects(&tree), vec!["user:alice"]);
+    }
+}
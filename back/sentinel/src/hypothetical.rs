@@ -0,0 +1,220 @@
+use std::sync::Arc;
+use uuid::Uuid;
+use crate::models::{RelationTuple, RelationTupleFilter, ChangelogEntry, ErasureReceipt, Operation, TupleUpdate};
+use crate::tuple_store::{TupleStore, TuplePage};
+use crate::tuple_tables::TupleTable;
+use crate::query_plan::QueryPlan;
+use crate::errors::{SentinelError, SentinelResult};
+
+/// 저장소 튜플 위에 가정한 변경(what-if)을 겹쳐 보여주는 읽기 전용 TupleStore
+/// Check 한 번의 평가에만 사용하며, 가정한 변경은 저장소에 반영하지 않음
+pub struct HypotheticalTupleStore {
+    base: Arc<dyn TupleStore>,
+    /// 가정한 삽입 (같은 키의 저장소 튜플을 대체)
+    inserts: Vec<RelationTuple>,
+    /// 가정한 삭제
+    deletes: Vec<RelationTuple>,
+}
+
+/// 튜플 키(namespace, object_id, relation, user_type, user_id)가 같은지 확인
+fn same_tuple(a: &RelationTuple, b: &RelationTuple) -> bool {
+    a.namespace == b.namespace
+        && a.object_id == b.object_id
+        && a.relation == b.relation
+        && a.user_type == b.user_type
+        && a.user_id == b.user_id
+}
+
+impl HypotheticalTupleStore {
+    /// 가정한 변경을 순서대로 적용 (같은 튜플을 여러 번 변경하면 마지막 변경이 남음)
+    /// Delete가 아닌 작업은 모두 삽입으로 취급
+    pub fn new(base: Arc<dyn TupleStore>, updates: &[TupleUpdate]) -> Self {
+        let mut inserts: Vec<RelationTuple> = Vec::new();
+        let mut deletes: Vec<RelationTuple> = Vec::new();
+        for update in updates {
+            let tuple = update.tuple.to_db_tuple();
+            inserts.retain(|t| !same_tuple(t, &tuple));
+            deletes.retain(|t| !same_tuple(t, &tuple));
+            match update.operation {
+                Operation::Delete => deletes.push(tuple),
+                _ => inserts.push(tuple),
+            }
+        }
+        Self { base, inserts, deletes }
+    }
+
+    /// 저장소 조회 결과에서 가정한 변경이 덮는 튜플을 빼고, 조건에 맞는 가정한 삽입을 더함
+    fn overlay(&self, stored: Vec<RelationTuple>, predicate: impl Fn(&RelationTuple) -> bool) -> Vec<RelationTuple> {
+        let mut tuples: Vec<RelationTuple> = stored.into_iter()
+            .filter(|t| !self.overrides(t))
+            .collect();
+        tuples.extend(self.inserted(predicate));
+        tuples
+    }
+
+    /// 가정한 삽입이나 삭제가 같은 키의 튜플을 바꾸는지
+    fn overrides(&self, tuple: &RelationTuple) -> bool {
+        self.inserts.iter().chain(&self.deletes).any(|t| same_tuple(t, tuple))
+    }
+
+    /// 조건에 맞고 만료되지 않은 가정한 삽입
+    fn inserted(&self, predicate: impl Fn(&RelationTuple) -> bool) -> Vec<RelationTuple> {
+        self.inserts.iter()
+            .filter(|t| predicate(t) && !t.is_expired())
+            .cloned()
+            .collect()
+    }
+
+    fn read_only() -> SentinelError {
+        SentinelError::internal_error("Hypothetical tuple store is read-only")
+    }
+}
+
+#[async_trait::async_trait]
+impl TupleStore for HypotheticalTupleStore {
+    async fn insert_tuple(&self, _tuple: &RelationTuple) -> SentinelResult<()> {
+        Err(Self::read_only())
+    }
+
+    async fn delete_tuple(&self, _tuple: &RelationTuple) -> SentinelResult<()> {
+        Err(Self::read_only())
+    }
+
+    async fn write_tuples(&self, _updates: &[(Operation, RelationTuple)]) -> SentinelResult<()> {
+        Err(Self::read_only())
+    }
+
+    async fn claim_tuple(&self, _tuple: &RelationTuple, _claim_id: Uuid) -> SentinelResult<bool> {
+        Err(Self::read_only())
+    }
+
+    async fn release_tuple(&self, _tuple: &RelationTuple, _claim_id: Uuid) -> SentinelResult<()> {
+        Err(Self::read_only())
+    }
+
+    async fn find_direct_tuple(&self, tuple: &RelationTuple) -> SentinelResult<Option<RelationTuple>> {
+        if self.deletes.iter().any(|t| same_tuple(t, tuple)) {
+            return Ok(None);
+        }
+        if let Some(inserted) = self.inserts.iter().find(|t| same_tuple(t, tuple)) {
+            return Ok(Some(inserted.clone()).filter(|t| !t.is_expired()));
+        }
+        self.base.find_direct_tuple(tuple).await
+    }
+
+    async fn find_tuples_by_object(
+        &self,
+        namespace: &str,
+        object_id: &str,
+    ) -> SentinelResult<Vec<RelationTuple>> {
+        let stored = self.base.find_tuples_by_object(namespace, object_id).await?;
+        Ok(self.overlay(stored, |t| t.namespace == namespace && t.object_id == object_id))
+    }
+
+    async fn find_tuples_by_object_relation(
+        &self,
+        namespace: &str,
+        object_id: &str,
+        relation: &str,
+    ) -> SentinelResult<Vec<RelationTuple>> {
+        let stored = self.base.find_tuples_by_object_relation(namespace, object_id, relation).await?;
+        Ok(self.overlay(stored, |t| t.namespace == namespace && t.object_id == object_id && t.relation == relation))
+    }
+
+    async fn find_user_memberships(&self, user_id: &str) -> SentinelResult<Vec<RelationTuple>> {
+        let stored = self.base.find_user_memberships(user_id).await?;
+        Ok(self.overlay(stored, |t| t.user_type == "user" && t.user_id == user_id))
+    }
+
+    async fn find_subject_memberships(&self, user_type: &str, user_id: &str) -> SentinelResult<Vec<RelationTuple>> {
+        let stored = self.base.find_subject_memberships(user_type, user_id).await?;
+        Ok(self.overlay(stored, |t| t.user_type == user_type && t.user_id == user_id))
+    }
+
+    async fn find_userset_members(
+        &self,
+        namespace: &str,
+        object_id: &str,
+        relation: &str,
+    ) -> SentinelResult<Vec<RelationTuple>> {
+        let stored = self.base.find_userset_members(namespace, object_id, relation).await?;
+        Ok(self.overlay(stored, |t| t.namespace == namespace && t.object_id == object_id && t.relation == relation))
+    }
+
+    /// 가정한 삭제로 관계의 마지막 튜플이 없어지는 경우는 확인하지 않음 (있다고 답함)
+    async fn stored_relations(&self, namespace: &str) -> SentinelResult<Vec<String>> {
+        let mut relations = self.base.stored_relations(namespace).await?;
+        relations.extend(self.inserted(|t| t.namespace == namespace).into_iter().map(|t| t.relation));
+        relations.sort();
+        relations.dedup();
+        Ok(relations)
+    }
+
+    /// 가정한 삽입은 첫 페이지에 더하므로 첫 페이지가 page_size보다 클 수 있음
+    async fn read_tuples(
+        &self,
+        filter: &RelationTupleFilter,
+        page_size: u32,
+        paging_state: Option<Vec<u8>>,
+    ) -> SentinelResult<TuplePage> {
+        let plan = QueryPlan::for_filter(filter)?;
+        let first_page = paging_state.is_none();
+        let page = self.base.read_tuples(filter, page_size, paging_state).await?;
+        let mut tuples: Vec<RelationTuple> = page.tuples.into_iter()
+            .filter(|t| !self.overrides(t))
+            .collect();
+        if first_page {
+            tuples.extend(self.inserted(|t| plan.matches(t)));
+        }
+        Ok(TuplePage { tuples, paging_state: page.paging_state })
+    }
+
+    /// 저장소의 행을 그대로 조회 (가정한 변경은 겹치지 않음)
+    async fn scan_table(
+        &self,
+        table: &'static TupleTable,
+        page_size: u32,
+        paging_state: Option<Vec<u8>>,
+    ) -> SentinelResult<TuplePage> {
+        self.base.scan_table(table, page_size, paging_state).await
+    }
+
+    /// 저장소의 행을 그대로 조회 (가정한 변경은 겹치지 않음)
+    async fn find_row(&self, table: &'static TupleTable, tuple: &RelationTuple) -> SentinelResult<Option<RelationTuple>> {
+        self.base.find_row(table, tuple).await
+    }
+
+    async fn write_row(&self, _table: &'static TupleTable, _tuple: &RelationTuple) -> SentinelResult<()> {
+        Err(Self::read_only())
+    }
+
+    async fn delete_row(&self, _table: &'static TupleTable, _tuple: &RelationTuple) -> SentinelResult<()> {
+        Err(Self::read_only())
+    }
+
+    async fn record_change(&self, _entry: &ChangelogEntry) -> SentinelResult<()> {
+        Err(Self::read_only())
+    }
+
+    async fn read_changes(&self, namespace: &str, after_revision: i64, limit: usize) -> SentinelResult<Vec<ChangelogEntry>> {
+        self.base.read_changes(namespace, after_revision, limit).await
+    }
+
+    async fn pseudonymize_changes(
+        &self,
+        _namespaces: &[String],
+        _user_type: &str,
+        _user_id: &str,
+        _pseudonym: &str,
+    ) -> SentinelResult<u64> {
+        Err(Self::read_only())
+    }
+
+    async fn record_erasure(&self, _receipt: &ErasureReceipt) -> SentinelResult<()> {
+        Err(Self::read_only())
+    }
+
+    async fn find_erasure(&self, receipt_id: Uuid) -> SentinelResult<Option<ErasureReceipt>> {
+        self.base.find_erasure(receipt_id).await
+    }
+}
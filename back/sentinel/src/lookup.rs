@@ -0,0 +1,407 @@
+use std::sync::Arc;
+use std::collections::{BTreeSet, HashSet, VecDeque};
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD as BASE64_URL};
+use futures::future::join_all;
+use tracing::info;
+use crate::models::{
+    CheckRequest, LookupResourcesRequest, LookupResourcesResponse,
+    LookupSubjectsRequest, LookupSubjectsResponse, RelationTuple, WILDCARD_SUBJECT_ID
+};
+use crate::tuple_store::TupleStore;
+use crate::namespace_store::NamespaceRegistry;
+use crate::permission_checker::PermissionChecker;
+use crate::cache::Cache;
+use crate::zookie::{Zookie, ZookieManager};
+use crate::errors::{SentinelError, SentinelResult};
+
+/// LookupSubjects 기본 페이지 크기
+const DEFAULT_PAGE_SIZE: usize = 100;
+/// LookupSubjects 최대 페이지 크기
+const MAX_PAGE_SIZE: usize = 1000;
+/// LookupResources에서 동시에 실행하는 최대 Check 수
+const CHECK_CONCURRENCY: usize = 100;
+
+/// 역방향 조회 엔진 (LookupResources, LookupSubjects)
+/// 인덱스 테이블로 후보를 찾은 뒤 각 후보를 Check로 검증하므로
+/// intersection/exclusion을 포함한 결과가 Check와 항상 일치
+pub struct Lookup<C: Cache> {
+    tuple_store: Arc<dyn TupleStore>,
+    namespaces: Arc<NamespaceRegistry>,
+    checker: PermissionChecker<C>,
+    zookie_manager: Arc<ZookieManager<C>>,
+}
+
+impl<C: Cache> Lookup<C> {
+    /// 새로운 Lookup 생성
+    pub fn new(
+        tuple_store: Arc<dyn TupleStore>,
+        namespaces: Arc<NamespaceRegistry>,
+        cache: Arc<C>,
+        zookie_manager: Arc<ZookieManager<C>>,
+    ) -> Self {
+        let checker = PermissionChecker::new(
+            tuple_store.clone(),
+            namespaces.clone(),
+            cache,
+            zookie_manager.clone(),
+        );
+        Self {
+            tuple_store,
+            namespaces,
+            checker,
+            zookie_manager,
+        }
+    }
+
+    /// 사용자가 관계를 가진 네임스페이스의 모든 객체 조회
+    pub async fn lookup_resources(&self, request: &LookupResourcesRequest) -> SentinelResult<LookupResourcesResponse> {
+        let snapshot_zookie = self.zookie_manager
+            .validate_and_get_snapshot_time(request.zookie.as_deref())
+            .await?;
+
+        let user_type = request.user_type.as_deref().unwrap_or("user");
+        let candidates = self.reachable_objects(user_type, &request.user_id, &request.namespace).await?;
+
+        // 저장소와 캐시에 한 번에 보내는 Check 수를 제한하도록 CHECK_CONCURRENCY개씩 병렬로 검증
+        let candidates: Vec<String> = candidates.into_iter().collect();
+        let mut object_ids = Vec::new();
+        for chunk in candidates.chunks(CHECK_CONCURRENCY) {
+            let checks = chunk.iter().map(|object_id| {
+                let check_request = CheckRequest {
+                    namespace: request.namespace.clone(),
+                    object_id: object_id.clone(),
+                    relation: request.relation.clone(),
+                    user_id: request.user_id.clone(),
+                    user_type: Some(user_type.to_string()),
+                    zookie: None,
+                    context: Default::default(),
+                    explain: false,
+                    hypothetical: Vec::new(),
+                };
+                self.check_candidate(check_request, &snapshot_zookie)
+            });
+            for (object_id, allowed) in chunk.iter().zip(join_all(checks).await) {
+                if allowed? {
+                    object_ids.push(object_id.clone());
+                }
+            }
+        }
+
+        info!(
+            "Lookup resources {}#{} for {}:{}: {}/{} candidates allowed",
+            request.namespace, request.relation, user_type, request.user_id, object_ids.len(), candidates.len()
+        );
+        Ok(LookupResourcesResponse {
+            object_ids,
+            zookie: snapshot_zookie.to_string()?,
+        })
+    }
+
+    /// 객체-관계를 가진 모든 사용자 조회 (user_id 순 페이지 단위)
+    /// 페이지마다 객체 그래프 전체를 다시 탐색하므로 비용은 페이지 크기가 아니라 도달 가능한 전체 사용자 수에 비례
+    pub async fn lookup_subjects(&self, request: &LookupSubjectsRequest) -> SentinelResult<LookupSubjectsResponse> {
+        let snapshot_zookie = self.zookie_manager
+            .validate_and_get_snapshot_time(request.zookie.as_deref())
+            .await?;
+
+        let page_size = request.page_size
+            .map_or(DEFAULT_PAGE_SIZE, |size| size as usize)
+            .clamp(1, MAX_PAGE_SIZE);
+        let after = request.page_token.as_deref().map(decode_page_token).transpose()?;
+
+        // 와일드카드 주체(user:*)는 사용자 ID가 아니므로 목록에서 빼고 wildcard로 따로 보고
+        let mut reachable = self.reachable_users(&request.namespace, &request.object_id).await?;
+        let wildcard = reachable.remove(WILDCARD_SUBJECT_ID)
+            && self.check_candidate(subject_check_request(request, WILDCARD_SUBJECT_ID), &snapshot_zookie).await?;
+        let candidates: Vec<String> = reachable
+            .into_iter()
+            .filter(|user_id| after.as_ref().is_none_or(|after| user_id > after))
+            .collect();
+
+        // 페이지가 찰 때까지 page_size개씩 병렬로 검증
+        let mut user_ids = Vec::new();
+        for chunk in candidates.chunks(page_size) {
+            let checks = chunk.iter().map(|user_id| {
+                self.check_candidate(subject_check_request(request, user_id), &snapshot_zookie)
+            });
+            for (user_id, allowed) in chunk.iter().zip(join_all(checks).await) {
+                if allowed? {
+                    user_ids.push(user_id.clone());
+                }
+            }
+            if user_ids.len() >= page_size {
+                break;
+            }
+        }
+        user_ids.truncate(page_size);
+
+        let next_page_token = match user_ids.last() {
+            Some(last) if user_ids.len() == page_size && candidates.last() != Some(last) => {
+                Some(BASE64_URL.encode(last))
+            }
+            _ => None,
+        };
+
+        info!(
+            "Lookup subjects {}:{}#{}: {} users (wildcard: {}, more: {})",
+            request.namespace, request.object_id, request.relation, user_ids.len(), wildcard, next_page_token.is_some()
+        );
+        Ok(LookupSubjectsResponse {
+            user_ids,
+            wildcard,
+            next_page_token,
+            zookie: snapshot_zookie.to_string()?,
+        })
+    }
+
+    /// 후보를 Check로 검증 (스냅샷 기준, 캐시 미사용)
+    async fn check_candidate(&self, request: CheckRequest, snapshot_zookie: &Zookie) -> SentinelResult<bool> {
+        let response = self.checker.check_permission_uncached(&request, snapshot_zookie).await?;
+        Ok(response.allowed)
+    }
+
+    /// 주체로부터 튜플을 거슬러 올라가 도달할 수 있는 객체 중 네임스페이스에 속한 것 (후보)
+    /// 주체나 같은 타입의 와일드카드가 저장된 튜플의 객체에서 시작해, 그 객체 자체(tuple_to_userset)나
+    /// 그 객체의 userset(예: teams:backend#member)이 주체로 저장된 튜플을 따라감
+    async fn reachable_objects(
+        &self,
+        user_type: &str,
+        user_id: &str,
+        namespace: &str,
+    ) -> SentinelResult<BTreeSet<String>> {
+        let mut visited: HashSet<(String, String)> = HashSet::new();
+        let mut queue: VecDeque<(String, String)> = VecDeque::new();
+        let mut candidates = BTreeSet::new();
+        let mut pending: Vec<RelationTuple> = self.tuple_store.find_subject_memberships(user_type, user_id).await?;
+        // 같은 타입의 모든 주체에게 허용된 튜플 (예: documents:doc1#viewer@user:*)
+        if user_type != "userset" && user_id != WILDCARD_SUBJECT_ID {
+            pending.extend(self.tuple_store.find_subject_memberships(user_type, WILDCARD_SUBJECT_ID).await?);
+        }
+
+        loop {
+            for tuple in pending.drain(..) {
+                let object = (tuple.namespace, tuple.object_id);
+                if visited.insert(object.clone()) {
+                    queue.push_back(object);
+                }
+            }
+            let Some((object_namespace, object_id)) = queue.pop_front() else {
+                break;
+            };
+            if object_namespace == namespace {
+                candidates.insert(object_id.clone());
+            }
+
+            // 객체 자체가 주체인 튜플 (예: documents:doc1#parent@folders:shared)
+            pending.extend(self.tuple_store.find_subject_memberships(&object_namespace, &object_id).await?);
+
+            // 객체의 userset이 주체인 튜플 (예: documents:doc1#editor@userset:teams:backend#member)
+            let schema = self.namespaces.get(&object_namespace).await?;
+            for relation in schema.config.relation_names() {
+                let userset = format!("{}:{}#{}", object_namespace, object_id, relation);
+                pending.extend(self.tuple_store.find_subject_memberships("userset", &userset).await?);
+            }
+        }
+
+        Ok(candidates)
+    }
+
+    /// 객체에서 튜플을 따라 내려가 도달할 수 있는 사용자 (후보)
+    /// 객체에 저장된 userset 주체(예: teams:backend#member)와 객체 주체(tuple_to_userset)를 따라감
+    async fn reachable_users(&self, namespace: &str, object_id: &str) -> SentinelResult<BTreeSet<String>> {
+        let mut visited: HashSet<(String, String)> = HashSet::new();
+        let mut queue: VecDeque<(String, String)> = VecDeque::new();
+        let mut candidates = BTreeSet::new();
+        visited.insert((namespace.to_string(), object_id.to_string()));
+        queue.push_back((namespace.to_string(), object_id.to_string()));
+
+        while let Some((object_namespace, object_id)) = queue.pop_front() {
+            for tuple in self.tuple_store.find_tuples_by_object(&object_namespace, &object_id).await? {
+                if tuple.is_direct_user() {
+                    candidates.insert(tuple.user_id);
+                    continue;
+                }
+                let Some((subject_namespace, subject_object)) = tuple.subject_object() else {
+                    continue;
+                };
+                let object = (subject_namespace.to_string(), subject_object.to_string());
+                if visited.insert(object.clone()) {
+                    queue.push_back(object);
+                }
+            }
+        }
+
+        Ok(candidates)
+    }
+}
+
+/// LookupSubjects 후보 사용자를 검증하는 Check 요청
+fn subject_check_request(request: &LookupSubjectsRequest, user_id: &str) -> CheckRequest {
+    CheckRequest {
+        namespace: request.namespace.clone(),
+        object_id: request.object_id.clone(),
+        relation: request.relation.clone(),
+        user_id: user_id.to_string(),
+        user_type: Some("user".to_string()),
+        zookie: None,
+        context: Default::default(),
+        explain: false,
+        hypothetical: Vec::new(),
+    }
+}
+
+/// 페이지 토큰 해석 (이전 페이지의 마지막 user_id)
+fn decode_page_token(token: &str) -> SentinelResult<String> {
+    BASE64_URL
+        .decode(token)
+        .ok()
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+        .ok_or_else(|| SentinelError::validation_error("Invalid page token"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{MemoryTupleStore, MockCache, MemoryNamespaceStore};
+
+    const DOCUMENTS: &str = r#"{
+        "name": "documents",
+        "relations": [
+            {"name": "owner"},
+            {"name": "parent"},
+            {"name": "banned"},
+            {"name": "viewer", "rewrite": {"exclusion": {
+                "base": {"union": [
+                    "this",
+                    {"computed_userset": {"relation": "owner"}},
+                    {"tuple_to_userset": {"tupleset": "parent", "computed_userset": "viewer"}}
+                ]},
+                "subtract": {"computed_userset": {"relation": "banned"}}
+            }}}
+        ]
+    }"#;
+
+    const FOLDERS: &str = r#"{"name": "folders", "relations": [{"name": "viewer"}]}"#;
+
+    const TEAMS: &str = r#"{"name": "teams", "hierarchy": ["owner", "member"]}"#;
+
+    fn lookup(tuples: &[&str]) -> Lookup<MockCache> {
+        let cache = Arc::new(MockCache::new());
+        let namespaces = MemoryNamespaceStore::from_json(&[DOCUMENTS, FOLDERS, TEAMS]);
+        Lookup::new(
+            Arc::new(MemoryTupleStore::with_tuples(tuples)),
+            Arc::new(NamespaceRegistry::new(Arc::new(namespaces))),
+            cache.clone(),
+            Arc::new(ZookieManager::new(cache, None)),
+        )
+    }
+
+    async fn resources(lookup: &Lookup<MockCache>, relation: &str, user_id: &str) -> Vec<String> {
+        let request = LookupResourcesRequest {
+            namespace: "documents".to_string(),
+            relation: relation.to_string(),
+            user_id: user_id.to_string(),
+            user_type: None,
+            zookie: None,
+        };
+        lookup.lookup_resources(&request).await.unwrap().object_ids
+    }
+
+    #[tokio::test]
+    async fn test_lookup_resources() {
+        let lookup = lookup(&[
+            "documents:doc1#owner@user:alice",
+            "documents:doc2#viewer@userset:teams:backend#member",
+            "documents:doc3#parent@folders:shared",
+            "documents:doc4#parent@folders:shared",
+            "documents:doc4#banned@user:alice",
+            "folders:shared#viewer@userset:teams:backend#member",
+            "teams:backend#owner@user:alice",
+            "teams:frontend#member@user:bob",
+        ]);
+
+        // 직접 권한, 팀 userset(상위 역할 owner), 폴더 상속 / banned는 제외
+        assert_eq!(resources(&lookup, "viewer", "alice").await, vec!["doc1", "doc2", "doc3"]);
+        assert_eq!(resources(&lookup, "owner", "alice").await, vec!["doc1"]);
+        assert!(resources(&lookup, "viewer", "bob").await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_lookup_resources_wildcard() {
+        let lookup = lookup(&[
+            "documents:doc1#viewer@user:*",
+            "documents:doc2#parent@folders:public",
+            "documents:doc3#viewer@user:*",
+            "documents:doc3#banned@user:alice",
+            "folders:public#viewer@user:*",
+        ]);
+
+        assert_eq!(resources(&lookup, "viewer", "alice").await, vec!["doc1", "doc2"]);
+        assert_eq!(resources(&lookup, "viewer", "bob").await, vec!["doc1", "doc2", "doc3"]);
+        assert!(resources(&lookup, "owner", "bob").await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_lookup_subjects() {
+        let lookup = lookup(&[
+            "documents:doc1#owner@user:alice",
+            "documents:doc1#viewer@userset:teams:backend#member",
+            "documents:doc1#parent@folders:shared",
+            "documents:doc1#banned@user:erin",
+            "folders:shared#viewer@user:dave",
+            "folders:shared#viewer@user:erin",
+            "teams:backend#owner@user:bob",
+            "teams:backend#member@user:carol",
+        ]);
+        let request = |page_token: Option<String>| LookupSubjectsRequest {
+            namespace: "documents".to_string(),
+            object_id: "doc1".to_string(),
+            relation: "viewer".to_string(),
+            page_size: Some(2),
+            page_token,
+            zookie: None,
+        };
+
+        // 팀 userset(owner 상속 포함)과 폴더 상속을 사용자 단위로 풀어서 반환, banned는 제외
+        let first = lookup.lookup_subjects(&request(None)).await.unwrap();
+        assert_eq!(first.user_ids, vec!["alice", "bob"]);
+        let second = lookup.lookup_subjects(&request(first.next_page_token)).await.unwrap();
+        assert_eq!(second.user_ids, vec!["carol", "dave"]);
+        // 남은 후보(erin)가 거부되면 마지막 페이지는 비어 있을 수 있음
+        let third = lookup.lookup_subjects(&request(second.next_page_token)).await.unwrap();
+        assert!(third.user_ids.is_empty());
+        assert!(third.next_page_token.is_none());
+
+        assert!(lookup.lookup_subjects(&request(Some("!".to_string()))).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_lookup_subjects_wildcard() {
+        let lookup = lookup(&[
+            "documents:doc1#viewer@user:*",
+            "documents:doc1#owner@user:alice",
+            "documents:doc1#banned@user:bob",
+            "documents:doc2#parent@folders:public",
+            "folders:public#viewer@user:*",
+        ]);
+        let request = |object_id: &str, relation: &str| LookupSubjectsRequest {
+            namespace: "documents".to_string(),
+            object_id: object_id.to_string(),
+            relation: relation.to_string(),
+            page_size: None,
+            page_token: None,
+            zookie: None,
+        };
+
+        // 와일드카드는 user_ids에 "*"로 섞지 않고 wildcard로 따로 보고
+        let viewers = lookup.lookup_subjects(&request("doc1", "viewer")).await.unwrap();
+        assert_eq!(viewers.user_ids, vec!["alice"]);
+        assert!(viewers.wildcard);
+        let inherited = lookup.lookup_subjects(&request("doc2", "viewer")).await.unwrap();
+        assert!(inherited.user_ids.is_empty());
+        assert!(inherited.wildcard);
+        let owners = lookup.lookup_subjects(&request("doc1", "owner")).await.unwrap();
+        assert_eq!(owners.user_ids, vec!["alice"]);
+        assert!(!owners.wildcard);
+    }
+}
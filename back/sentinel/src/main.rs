@@ -8,17 +8,37 @@ use std::sync::Arc;
 use tracing::{info, error};
 use cache::Cache;
 use zookie::ZookieManager;
+use namespace_store::{NamespaceRegistry, ScyllaNamespaceStore};
+use page_token::PageTokenCodec;
+use tuple_store::ScyllaTupleStore;
 
 mod database;
 mod errors;
 mod models;
 mod tuple_store;
+mod tuple_tables;
+mod query_plan;
+mod precondition;
+mod tuple_writer;
+mod hypothetical;
+mod reconciler;
+mod erasure;
+mod caveat;
+mod namespace_config;
+mod namespace_store;
 mod permission_hierarchy;
 mod permission_checker;
+mod expander;
+mod lookup;
+mod watch;
+mod page_token;
 mod api_handlers;
 mod cache;
 mod zookie;
 
+#[cfg(test)]
+mod test_utils;
+
 // App State to hold database connections
 #[derive(Clone)]
 pub struct AppState {
@@ -26,6 +46,8 @@ pub struct AppState {
     pub redis: Arc<RedisClient>,
     pub cache: Arc<cache::RedisCache>,
     pub zookie_manager: Arc<ZookieManager<cache::RedisCache>>,
+    pub namespaces: Arc<NamespaceRegistry>,
+    pub page_tokens: Arc<PageTokenCodec>,
 }
 
 // Health check endpoint
@@ -140,6 +162,14 @@ async fn main() -> std::io::Result<()> {
         .unwrap_or_else(|_| "50006".to_string())
         .parse::<u16>()
         .expect("Invalid REDIS_PORT");
+    let namespace_reload_secs = env::var("NAMESPACE_RELOAD_INTERVAL_SECS")
+        .unwrap_or_else(|_| "30".to_string())
+        .parse::<u64>()
+        .expect("Invalid NAMESPACE_RELOAD_INTERVAL_SECS");
+    let reconcile_interval_secs = env::var("INDEX_RECONCILE_INTERVAL_SECS")
+        .unwrap_or_else(|_| "86400".to_string())
+        .parse::<u64>()
+        .expect("Invalid INDEX_RECONCILE_INTERVAL_SECS");
     let port = env::var("PORT")
         .unwrap_or_else(|_| "15004".to_string())
         .parse::<u16>()
@@ -176,12 +206,38 @@ async fn main() -> std::io::Result<()> {
     // Initialize Zookie manager
     let node_id = env::var("NODE_ID").ok();
     let zookie_manager = Arc::new(ZookieManager::new(cache.clone(), node_id));
+
+    // Initialize namespace config registry
+    let namespace_store = Arc::new(ScyllaNamespaceStore::new(session.clone()));
+    let namespaces = Arc::new(NamespaceRegistry::new(namespace_store));
+    if let Err(e) = namespaces.register_builtin().await {
+        error!("Failed to register builtin namespaces: {}", e);
+        std::process::exit(1);
+    }
+    let reload_checker = permission_checker::PermissionChecker::new(
+        Arc::new(ScyllaTupleStore::new(session.clone())),
+        namespaces.clone(),
+        cache.clone(),
+        zookie_manager.clone(),
+    );
+    tokio::spawn(namespaces.clone().run_reload_loop(
+        reload_checker,
+        std::time::Duration::from_secs(namespace_reload_secs),
+    ));
+    
+    // Initialize index reconciler (0이면 주기 실행 안 함, /api/v1/reconcile로만 실행)
+    if reconcile_interval_secs > 0 {
+        let reconciler = Arc::new(reconciler::IndexReconciler::new(Arc::new(ScyllaTupleStore::new(session.clone()))));
+        tokio::spawn(reconciler.run_reconcile_loop(std::time::Duration::from_secs(reconcile_interval_secs)));
+    }
     
     let app_state = AppState {
         session: session.clone(),
         redis: redis.clone(),
         cache: cache.clone(),
         zookie_manager,
+        namespaces,
+        page_tokens: Arc::new(PageTokenCodec::from_env()),
     };
 
     info!("Starting Sentinel server on port {}", port);
@@ -207,11 +263,28 @@ async fn main() -> std::io::Result<()> {
                     .route("/check", web::post().to(api_handlers::check_permission))
                     .route("/write", web::post().to(api_handlers::write_permissions))
                     .route("/read", web::post().to(api_handlers::read_permissions))
+                    .route("/delete", web::post().to(api_handlers::delete_relationships))
+                    .route("/objects/{namespace}/{object_id}", web::delete().to(api_handlers::delete_object_relationships))
+                    .route("/users/{user_id}/erase", web::post().to(api_handlers::erase_user))
+                    .route("/erasures/{receipt_id}", web::get().to(api_handlers::get_erasure))
                     .route("/batch_check", web::post().to(api_handlers::batch_check_permissions))
+                    .route("/expand", web::post().to(api_handlers::expand))
+                    .route("/lookup_resources", web::post().to(api_handlers::lookup_resources))
+                    .route("/lookup_subjects", web::post().to(api_handlers::lookup_subjects))
+                    .route("/watch", web::get().to(api_handlers::watch))
+
+                    // Namespace Config API
+                    .route("/namespaces", web::get().to(api_handlers::list_namespaces))
+                    .route("/namespaces", web::post().to(api_handlers::create_namespace))
+                    .route("/namespaces/{name}", web::get().to(api_handlers::get_namespace))
+                    .route("/namespaces/{name}", web::put().to(api_handlers::update_namespace))
+                    .route("/namespaces/{name}", web::delete().to(api_handlers::delete_namespace))
+                    .route("/namespaces/{name}/versions/{version}", web::get().to(api_handlers::get_namespace_version))
                     
                     // Debug/Utility APIs
                     .route("/users/{user_id}/permissions", web::get().to(api_handlers::get_user_permissions))
                     .route("/objects/{namespace}/{object_id}/permissions", web::get().to(api_handlers::get_object_permissions))
+                    .route("/reconcile", web::post().to(api_handlers::reconcile_indexes))
             )
     })
     .bind(format!("0.0.0.0:{}", port))?
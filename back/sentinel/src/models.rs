@@ -1,11 +1,18 @@
+use std::sync::atomic::{AtomicI64, Ordering};
 use serde::{Deserialize, Serialize};
 use scylla::{DeserializeRow, SerializeRow};
 use scylla::value::CqlTimestamp;
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
+use crate::namespace_config::NamespaceConfig;
+use crate::caveat::{Caveat, CheckContext};
+use crate::permission_hierarchy::PermissionSource;
+
+/// 와일드카드 주체 ID (예: documents:doc1#viewer@user:* - 모든 사용자)
+pub const WILDCARD_SUBJECT_ID: &str = "*";
 
 /// Zanzibar 권한 튜플을 나타내는 구조체 (데이터베이스 저장용)
-/// 스키마: relation_tuples (namespace, object_id, relation, user_type, user_id, created_at)
+/// 스키마: relation_tuples (namespace, object_id, relation, user_type, user_id, created_at, expires_at, caveat)
 #[derive(Debug, Clone, PartialEq, Eq, SerializeRow, DeserializeRow)]
 pub struct RelationTuple {
     /// 네임스페이스 (예: "document", "team", "project")
@@ -20,6 +27,10 @@ pub struct RelationTuple {
     pub user_id: String,
     /// 생성 시간
     pub created_at: CqlTimestamp,
+    /// 만료 시간 (없으면 만료되지 않음)
+    pub expires_at: Option<CqlTimestamp>,
+    /// 조건 (Caveat JSON, 없으면 무조건 권한 부여)
+    pub caveat: Option<String>,
 }
 
 /// API 요청/응답에서 사용하는 권한 튜플 구조체
@@ -37,6 +48,12 @@ pub struct ApiRelationTuple {
     pub user_id: String,
     /// 생성 시간
     pub created_at: DateTime<Utc>,
+    /// 만료 시간 (선택적, 지나면 권한 검증과 조회에서 제외되고 저장소에서도 삭제됨)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// 조건 (선택적, Check 시점에 요청 context로 평가해 만족할 때만 권한 부여)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub caveat: Option<Caveat>,
 }
 
 impl RelationTuple {
@@ -55,9 +72,33 @@ impl RelationTuple {
             user_type: user_type.into(),
             user_id: user_id.into(),
             created_at: CqlTimestamp(chrono::Utc::now().timestamp_millis()),
+            expires_at: None,
+            caveat: None,
         }
     }
 
+    /// 만료 시간 지정
+    pub fn with_expiry(mut self, expires_at: Option<CqlTimestamp>) -> Self {
+        self.expires_at = expires_at;
+        self
+    }
+
+    /// 조건 지정
+    pub fn with_caveat(mut self, caveat: Option<&Caveat>) -> Self {
+        self.caveat = caveat.map(Caveat::to_json);
+        self
+    }
+
+    /// now_millis 시점에 만료되었는지 확인
+    pub fn is_expired_at(&self, now_millis: i64) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at.0 <= now_millis)
+    }
+
+    /// 현재 만료되었는지 확인
+    pub fn is_expired(&self) -> bool {
+        self.is_expired_at(chrono::Utc::now().timestamp_millis())
+    }
+
     /// ApiRelationTuple로 변환
     pub fn to_api_tuple(&self) -> ApiRelationTuple {
         ApiRelationTuple {
@@ -67,7 +108,9 @@ impl RelationTuple {
             user_type: self.user_type.clone(),
             user_id: self.user_id.clone(),
             created_at: DateTime::from_timestamp_millis(self.created_at.0)
-                .unwrap_or_else(|| chrono::Utc::now()),
+                .unwrap_or_else(chrono::Utc::now),
+            expires_at: self.expires_at.and_then(|expires_at| DateTime::from_timestamp_millis(expires_at.0)),
+            caveat: self.caveat.as_deref().and_then(|caveat| Caveat::from_json(caveat).ok()),
         }
     }
 
@@ -88,6 +131,51 @@ impl RelationTuple {
     pub fn is_userset(&self) -> bool {
         self.user_type != "user"
     }
+
+    /// userset 주체 파싱: "teams:backend#member" -> (teams, backend, member)
+    /// user_type이 "userset"이 아니거나 형식이 잘못된 경우 None
+    pub fn userset_reference(&self) -> Option<(&str, &str, &str)> {
+        if self.user_type != "userset" {
+            return None;
+        }
+        let (namespace, object_relation) = self.user_id.split_once(':')?;
+        let (object_id, relation) = object_relation.split_once('#')?;
+        Some((namespace, object_id, relation))
+    }
+
+    /// 같은 타입의 모든 주체를 뜻하는 와일드카드 주체인지 확인 (예: @user:*)
+    pub fn is_wildcard(&self) -> bool {
+        self.user_type != "userset" && self.user_id == WILDCARD_SUBJECT_ID
+    }
+
+    /// 네임스페이스 설정의 subject_types와 비교할 주체 타입
+    /// 예: @user:alice -> "user", @user:* -> "user:*", @userset:teams:backend#member -> "teams#member", @folders:x -> "folders"
+    /// userset 형식이 잘못된 경우 None
+    pub fn subject_type(&self) -> Option<String> {
+        if self.user_type == "userset" {
+            self.userset_reference()
+                .map(|(namespace, _, relation)| format!("{}#{}", namespace, relation))
+        } else if self.is_wildcard() {
+            Some(format!("{}:{}", self.user_type, WILDCARD_SUBJECT_ID))
+        } else {
+            Some(self.user_type.clone())
+        }
+    }
+
+    /// 주체가 가리키는 객체 (tuple_to_userset의 tupleset 튜플용)
+    /// 예: document:doc1#parent@folder:x -> (folder, x)
+    ///     document:doc1#parent@userset:folder:x#... -> (folder, x)
+    /// 와일드카드 주체는 특정 객체가 아니므로 None
+    pub fn subject_object(&self) -> Option<(&str, &str)> {
+        if self.is_wildcard() {
+            return None;
+        }
+        match self.user_type.as_str() {
+            "user" => None,
+            "userset" => self.userset_reference().map(|(namespace, object_id, _)| (namespace, object_id)),
+            _ => Some((self.user_type.as_str(), self.user_id.as_str())),
+        }
+    }
 }
 
 impl ApiRelationTuple {
@@ -100,6 +188,8 @@ impl ApiRelationTuple {
             user_type: self.user_type.clone(),
             user_id: self.user_id.clone(),
             created_at: CqlTimestamp(self.created_at.timestamp_millis()),
+            expires_at: self.expires_at.map(|expires_at| CqlTimestamp(expires_at.timestamp_millis())),
+            caveat: self.caveat.as_ref().map(Caveat::to_json),
         }
     }
 }
@@ -119,23 +209,138 @@ pub struct CheckRequest {
     pub user_type: Option<String>,
     /// 일관성 토큰 (선택적)
     pub zookie: Option<String>,
+    /// 조건부 튜플 평가에 사용하는 값 (선택적, 예: {"ip": "10.0.0.5"})
+    #[serde(default, skip_serializing_if = "CheckContext::is_empty")]
+    pub context: CheckContext,
+    /// 권한 해석 경로를 응답에 포함할지 (디버깅용, 기본값: false)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub explain: bool,
+    /// 이번 평가에만 저장소에 겹쳐 적용할 가정한 변경 (선택적, 저장하거나 캐시하지 않음)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hypothetical: Vec<TupleUpdate>,
+}
+
+/// 권한 판정
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Permissionship {
+    Allowed,
+    Denied,
+    /// 조건부 튜플을 평가할 context가 부족해 판정할 수 없음
+    Conditional,
+}
+
+impl Permissionship {
+    pub fn from_allowed(allowed: bool) -> Self {
+        if allowed { Permissionship::Allowed } else { Permissionship::Denied }
+    }
+
+    pub fn is_allowed(self) -> bool {
+        self == Permissionship::Allowed
+    }
+
+    /// 합집합: 하나라도 허용이면 허용, 아니면 하나라도 조건부면 조건부
+    pub fn or(self, other: Self) -> Self {
+        match (self, other) {
+            (Permissionship::Allowed, _) | (_, Permissionship::Allowed) => Permissionship::Allowed,
+            (Permissionship::Conditional, _) | (_, Permissionship::Conditional) => Permissionship::Conditional,
+            _ => Permissionship::Denied,
+        }
+    }
+
+    /// 교집합: 하나라도 거부면 거부, 아니면 하나라도 조건부면 조건부
+    pub fn and(self, other: Self) -> Self {
+        match (self, other) {
+            (Permissionship::Denied, _) | (_, Permissionship::Denied) => Permissionship::Denied,
+            (Permissionship::Conditional, _) | (_, Permissionship::Conditional) => Permissionship::Conditional,
+            _ => Permissionship::Allowed,
+        }
+    }
+
+    /// 제외 규칙의 subtract 결과 뒤집기 (조건부는 그대로)
+    pub fn negate(self) -> Self {
+        match self {
+            Permissionship::Allowed => Permissionship::Denied,
+            Permissionship::Denied => Permissionship::Allowed,
+            Permissionship::Conditional => Permissionship::Conditional,
+        }
+    }
 }
 
 /// 권한 체크 응답
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CheckResponse {
-    /// 권한 허용 여부
+    /// 권한 허용 여부 (conditional이면 false)
     pub allowed: bool,
+    /// 권한 판정 (allowed / denied / conditional)
+    pub permissionship: Permissionship,
+    /// conditional일 때 판정에 필요한 context 필드
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub missing_context: Vec<String>,
     /// 응답 시간의 일관성 토큰
     pub zookie: String,
+    /// 권한 해석 경로 (explain 요청 시)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<CheckExplanation>,
+}
+
+/// Check 결과 설명 (explain)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckExplanation {
+    /// 응답의 결과를 Redis 캐시에서 읽었는지
+    pub cached: bool,
+    /// 저장소 기준으로 다시 평가한 판정 (캐시된 결과와 다르면 캐시가 오래된 것)
+    pub permissionship: Permissionship,
+    /// 권한을 부여한 경로 (요청한 관계에서 일치한 튜플까지, 허용된 경우)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub path: Vec<ExplainStep>,
+    /// exclusion으로 거부된 경우 제외 규칙에서 일치한 경로
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub excluded_by: Vec<ExplainStep>,
+    /// 사용자가 가진 가장 높은 권한 (요청한 네임스페이스의 권한 계층 기준)
+    pub user_max_permission: Option<String>,
+    /// 평가 중 찾은 권한 소스 (직접 권한, 팀 권한)
+    pub permission_sources: Vec<PermissionSource>,
+}
+
+/// 권한 해석 경로의 한 단계
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExplainStep {
+    /// 평가한 관계 (예: "documents:doc1#viewer")
+    pub userset: String,
+    /// 다음 단계로 넘어간 방법
+    pub via: ExplainVia,
+    /// 단계를 연결한 튜플 (예: "documents:doc1#editor@userset:teams:backend#member")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tuple: Option<String>,
+}
+
+/// 권한 해석 경로의 연결 방법
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExplainVia {
+    /// 주체가 직접 저장된 튜플 (와일드카드 포함, 경로의 마지막 단계)
+    Direct,
+    /// 같은 객체의 다른 관계 (computed_userset)
+    ComputedUserset,
+    /// 권한 계층의 상위 관계
+    Hierarchy,
+    /// userset 주체 튜플 (예: 팀 멤버)
+    Userset,
+    /// tupleset으로 연결된 객체 (tuple_to_userset)
+    TupleToUserset,
 }
 
 /// 권한 튜플 쓰기 작업 타입
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Operation {
-    /// 튜플 추가
+    /// 튜플 추가 (기존 클라이언트 호환용, Touch와 같음)
     Insert,
-    /// 튜플 삭제
+    /// 튜플이 없으면 추가, 있으면 그대로 둠
+    Touch,
+    /// 튜플 추가 (이미 있으면 쓰기 전체 실패)
+    Create,
+    /// 튜플 삭제 (없으면 아무것도 하지 않음)
     Delete,
 }
 
@@ -158,14 +363,26 @@ pub struct TupleUpdate {
 }
 
 /// 쓰기 작업의 선행 조건
+/// 모든 업데이트보다 먼저 평가되며, 하나라도 만족하지 않으면 쓰기 전체가 중단됨
+/// 평가와 반영은 원자적이지 않음 (check_preconditions 참고)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Precondition {
-    /// 작업 타입
-    pub operation: Operation,
+    /// 조건 종류
+    pub operation: PreconditionOperation,
     /// 필터 조건
     pub filter: RelationTupleFilter,
 }
 
+/// 선행 조건 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PreconditionOperation {
+    /// 필터에 맞는 튜플이 하나 이상 있어야 함
+    MustMatch,
+    /// 필터에 맞는 튜플이 없어야 함
+    MustNotMatch,
+}
+
 /// 튜플 필터 조건
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RelationTupleFilter {
@@ -186,6 +403,95 @@ pub struct RelationTupleFilter {
 pub struct WriteResponse {
     /// 응답 시간의 일관성 토큰
     pub zookie: String,
+    /// 업데이트별 결과 (updates와 같은 순서)
+    pub results: Vec<UpdateResult>,
+}
+
+/// 업데이트 하나의 결과
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateResult {
+    /// 요청한 작업 타입
+    pub operation: Operation,
+    /// 업데이트가 저장소에 미친 영향
+    pub outcome: UpdateOutcome,
+}
+
+/// 업데이트가 저장소에 미친 영향
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateOutcome {
+    /// 새 튜플이 추가됨
+    Created,
+    /// 만료 시간과 조건이 같은 튜플이 이미 있어 변경 없음 (Insert/Touch)
+    Unchanged,
+    /// 이미 있는 튜플의 만료 시간이나 조건(caveat)이 바뀜, created_at은 유지 (Insert/Touch)
+    Updated,
+    /// 튜플이 삭제됨
+    Deleted,
+    /// 삭제할 튜플이 없어 변경 없음
+    NotFound,
+}
+
+/// 필터로 권한 튜플 삭제 요청
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteRequest {
+    /// 삭제할 튜플 필터 (Read API와 같은 형식)
+    pub tuple_filter: RelationTupleFilter,
+    /// 최대 삭제 수 (선택적, 없으면 모두 삭제)
+    pub limit: Option<u32>,
+}
+
+/// 필터로 권한 튜플 삭제 응답
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteResponse {
+    /// 삭제된 튜플 수
+    pub deleted_count: u64,
+    /// 필터에 맞는 튜플이 모두 삭제되었는지 (limit에 걸리면 false)
+    pub complete: bool,
+    /// 응답 시간의 일관성 토큰
+    pub zookie: String,
+}
+
+/// 객체 삭제 응답
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteObjectResponse {
+    pub namespace: String,
+    pub object_id: String,
+    /// 객체가 리소스로 쓰인 튜플 중 삭제된 수 (namespace:object_id#*)
+    pub resource_tuples: u64,
+    /// 객체를 주체로 참조하던 튜플 중 삭제된 수 (userset 또는 객체 주체)
+    pub subject_tuples: u64,
+    /// 응답 시간의 일관성 토큰
+    pub zookie: String,
+}
+
+/// 사용자 삭제(잊혀질 권리) 영수증
+/// 원래 user_id는 담지 않으며, 호출자가 receipt_id와 사용자의 대응을 보관
+/// 스키마: erasure_receipts (receipt_id, receipt, erased_at) - receipt는 이 구조체의 JSON
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErasureReceipt {
+    /// 영수증 ID
+    pub receipt_id: Uuid,
+    /// 변경 이력에서 user_id 대신 기록된 값 ("erased:{receipt_id}")
+    pub pseudonym: String,
+    /// 삭제 시간
+    pub erased_at: DateTime<Utc>,
+    /// 삭제된 튜플 수
+    pub deleted_tuples: u64,
+    /// 삭제된 튜플이 있던 네임스페이스 (정렬, 중복 없음)
+    pub namespaces: Vec<String>,
+    /// user_id를 pseudonym으로 바꾼 변경 이력 수 (이번 삭제로 기록된 이력 포함)
+    pub pseudonymized_changes: u64,
+    /// check:*@user:{user_id} 캐시 무효화 성공 여부 (실패하면 캐시 TTL이 지나야 만료)
+    pub cache_invalidated: bool,
+}
+
+/// 사용자 삭제 응답
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EraseUserResponse {
+    pub receipt: ErasureReceipt,
+    /// 응답 시간의 일관성 토큰
+    pub zookie: String,
 }
 
 /// 권한 튜플 읽기 요청
@@ -212,14 +518,42 @@ pub struct ReadResponse {
     pub zookie: String,
 }
 
+/// changelog 파티션 하나가 담는 시간 범위 (1시간, 마이크로초)
+pub const CHANGELOG_BUCKET_MICROS: i64 = 3_600_000_000;
+
+/// changelog 보관 기간 (7일, 마이크로초) - 테이블 TTL로 삭제되며 이보다 오래된 시점부터는 Watch할 수 없음
+pub const CHANGELOG_RETENTION_MICROS: i64 = 7 * 24 * CHANGELOG_BUCKET_MICROS;
+
+/// 마지막으로 발급한 changelog revision
+static LAST_REVISION: AtomicI64 = AtomicI64::new(0);
+
+/// 다음 changelog revision 발급
+/// 마이크로초 타임스탬프 기반이며 프로세스 안에서는 항상 증가 (zookie의 timestamp_micros와 같은 단위)
+pub fn next_revision() -> i64 {
+    let now = chrono::Utc::now().timestamp_micros();
+    let previous = LAST_REVISION
+        .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |last| Some(now.max(last + 1)))
+        .unwrap_or_else(|last| last);
+    now.max(previous + 1)
+}
+
+/// revision이 속한 changelog 파티션 bucket
+pub fn changelog_bucket(revision: i64) -> i64 {
+    revision.div_euclid(CHANGELOG_BUCKET_MICROS)
+}
+
 /// 변경 이력 기록용 구조체 (데이터베이스 저장용)
-/// 스키마: changelog (id, namespace, object_id, relation, user_type, user_id, operation, timestamp)
+/// 스키마: changelog_by_namespace (namespace, bucket, revision, id, object_id, relation, user_type, user_id, operation, timestamp)
 #[derive(Debug, Clone, SerializeRow, DeserializeRow)]
 pub struct ChangelogEntry {
+    /// 네임스페이스 (파티션 키)
+    pub namespace: String,
+    /// 시간 구간 (파티션 키, revision / CHANGELOG_BUCKET_MICROS)
+    pub bucket: i64,
+    /// 단조 증가하는 변경 번호 (마이크로초 타임스탬프 기반)
+    pub revision: i64,
     /// 고유 ID
     pub id: Uuid,
-    /// 네임스페이스
-    pub namespace: String,
     /// 객체 ID
     pub object_id: String,
     /// 관계
@@ -235,20 +569,49 @@ pub struct ChangelogEntry {
 }
 
 impl ChangelogEntry {
-    /// 새로운 변경 이력 생성
+    /// 새로운 변경 이력 생성 (새 revision 발급)
     pub fn new(tuple: &RelationTuple, operation: &Operation) -> Self {
+        Self::with_revision(tuple, operation, next_revision())
+    }
+
+    /// 지정한 revision으로 변경 이력 생성
+    pub fn with_revision(tuple: &RelationTuple, operation: &Operation, revision: i64) -> Self {
         Self {
-            id: Uuid::new_v4(),
             namespace: tuple.namespace.clone(),
+            bucket: changelog_bucket(revision),
+            revision,
+            id: Uuid::new_v4(),
             object_id: tuple.object_id.clone(),
             relation: tuple.relation.clone(),  
             user_type: tuple.user_type.clone(),
             user_id: tuple.user_id.clone(),
             operation: match operation {
-                Operation::Insert => "INSERT".to_string(),
                 Operation::Delete => "DELETE".to_string(),
+                _ => "INSERT".to_string(),
             },
-            timestamp: CqlTimestamp(chrono::Utc::now().timestamp_millis()),
+            timestamp: CqlTimestamp(revision.div_euclid(1000)),
+        }
+    }
+
+    /// 변경된 튜플 (created_at은 변경 시간)
+    pub fn to_tuple(&self) -> RelationTuple {
+        RelationTuple {
+            namespace: self.namespace.clone(),
+            object_id: self.object_id.clone(),
+            relation: self.relation.clone(),
+            user_type: self.user_type.clone(),
+            user_id: self.user_id.clone(),
+            created_at: self.timestamp,
+            expires_at: None,
+            caveat: None,
+        }
+    }
+
+    /// 작업 타입
+    pub fn to_operation(&self) -> Operation {
+        match self.operation.as_str() {
+            "INSERT" => Operation::Insert,
+            _ => Operation::Delete,
         }
     }
 }
@@ -260,6 +623,9 @@ pub struct BatchCheckRequest {
     pub checks: Vec<CheckRequest>,
     /// 일관성 토큰 (선택적)
     pub zookie: Option<String>,
+    /// 모든 체크에 겹쳐 적용할 가정한 변경 (선택적, 각 체크의 hypothetical보다 먼저 적용)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hypothetical: Vec<TupleUpdate>,
 }
 
 /// 개별 권한 체크 결과
@@ -269,6 +635,11 @@ pub struct BatchCheckItem {
     pub request_index: usize,
     /// 권한 허용 여부
     pub allowed: bool,
+    /// 권한 판정 (allowed / denied / conditional)
+    pub permissionship: Permissionship,
+    /// conditional일 때 판정에 필요한 context 필드
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub missing_context: Vec<String>,
     /// 요청 정보 (디버깅용)
     pub request_info: String,
 }
@@ -303,4 +674,249 @@ impl BatchCheckResponse {
             zookie: format!("{}", chrono::Utc::now().timestamp_millis()),
         }
     }
-}
\ No newline at end of file
+}
+
+/// 저장된 네임스페이스 설정 (namespaces 테이블의 한 행)
+/// 스키마: namespaces (name, config, version, created_at, updated_at)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NamespaceRecord {
+    /// 네임스페이스 설정
+    pub config: NamespaceConfig,
+    /// 설정 버전 (등록 시 1, 수정할 때마다 1씩 증가)
+    pub version: i32,
+    /// 등록 시간
+    pub created_at: DateTime<Utc>,
+    /// 마지막 수정 시간
+    pub updated_at: DateTime<Utc>,
+}
+
+/// 네임스페이스 설정 수정 요청
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateNamespaceRequest {
+    /// 새 설정 (name은 경로의 네임스페이스와 같아야 함)
+    pub config: NamespaceConfig,
+    /// 기대하는 현재 버전 (선택적, 지정 시 다르면 409 Conflict)
+    pub expected_version: Option<i32>,
+}
+
+/// Expand 요청 (namespace:object_id#relation의 userset 트리 조회)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExpandRequest {
+    /// 네임스페이스
+    pub namespace: String,
+    /// 객체 ID
+    pub object_id: String,
+    /// 관계
+    pub relation: String,
+    /// 최대 확장 깊이 (선택적, 기본값 10)
+    pub max_depth: Option<u32>,
+    /// 일관성 토큰 (선택적)
+    pub zookie: Option<String>,
+}
+
+/// Expand 트리 노드의 연산 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExpandOperation {
+    /// 직접 저장된 주체 (subjects) + userset 주체를 확장한 노드 (children)
+    This,
+    /// 같은 객체의 다른 관계 (children 1개)
+    ComputedUserset,
+    /// tupleset으로 연결된 객체들의 관계 (연결된 객체마다 children 1개)
+    TupleToUserset,
+    /// children 중 하나라도 포함
+    Union,
+    /// children 모두에 포함
+    Intersection,
+    /// children[0]에 포함되고 children[1]에는 포함되지 않음
+    Exclusion,
+}
+
+/// Expand 트리 노드
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExpandNode {
+    /// 노드가 나타내는 userset (예: "documents:doc1#viewer")
+    pub userset: String,
+    /// 연산 종류
+    pub operation: ExpandOperation,
+    /// 직접 저장된 주체들 (예: "user:alice", "folders:shared", 모든 사용자는 "user:*")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subjects: Vec<String>,
+    /// 하위 노드들
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<ExpandNode>,
+    /// 최대 깊이에 도달해 더 이상 확장하지 않은 노드
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
+}
+
+impl ExpandNode {
+    /// 새로운 ExpandNode 생성
+    pub fn new(userset: impl Into<String>, operation: ExpandOperation) -> Self {
+        Self {
+            userset: userset.into(),
+            operation,
+            subjects: Vec::new(),
+            children: Vec::new(),
+            truncated: false,
+        }
+    }
+
+    /// 하위 노드를 포함한 ExpandNode 생성
+    pub fn with_children(userset: impl Into<String>, operation: ExpandOperation, children: Vec<ExpandNode>) -> Self {
+        Self {
+            children,
+            ..Self::new(userset, operation)
+        }
+    }
+}
+
+/// Expand 응답
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExpandResponse {
+    /// userset 트리
+    pub tree: ExpandNode,
+    /// 응답 시간의 일관성 토큰
+    pub zookie: String,
+}
+
+/// LookupResources 요청 (사용자가 관계를 가진 모든 객체 조회)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LookupResourcesRequest {
+    /// 조회할 객체의 네임스페이스
+    pub namespace: String,
+    /// 관계
+    pub relation: String,
+    /// 사용자 ID
+    pub user_id: String,
+    /// 사용자 타입 (선택적, 기본값: "user")
+    pub user_type: Option<String>,
+    /// 일관성 토큰 (선택적)
+    pub zookie: Option<String>,
+}
+
+/// LookupResources 응답
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LookupResourcesResponse {
+    /// Check가 허용하는 객체 ID 목록 (정렬됨)
+    pub object_ids: Vec<String>,
+    /// 응답 시간의 일관성 토큰
+    pub zookie: String,
+}
+
+/// LookupSubjects 요청 (객체-관계를 가진 모든 사용자 조회)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LookupSubjectsRequest {
+    /// 네임스페이스
+    pub namespace: String,
+    /// 객체 ID
+    pub object_id: String,
+    /// 관계
+    pub relation: String,
+    /// 페이지 크기 (선택적, 기본값 100)
+    pub page_size: Option<u32>,
+    /// 이전 응답의 next_page_token (선택적)
+    pub page_token: Option<String>,
+    /// 일관성 토큰 (선택적)
+    pub zookie: Option<String>,
+}
+
+/// LookupSubjects 응답
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LookupSubjectsResponse {
+    /// Check가 허용하는 사용자 ID 목록 (정렬됨)
+    pub user_ids: Vec<String>,
+    /// 와일드카드 튜플(user:*)로 exclusion에 제외되지 않은 모든 사용자가 허용되는지 (user_ids에는 포함하지 않음)
+    pub wildcard: bool,
+    /// 다음 페이지 토큰 (마지막 페이지면 None)
+    pub next_page_token: Option<String>,
+    /// 응답 시간의 일관성 토큰
+    pub zookie: String,
+}
+
+/// Watch 요청 (쿼리 파라미터)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchRequest {
+    /// 변경을 구독할 네임스페이스
+    pub namespace: String,
+    /// 이 시점 이후의 변경부터 전송 (선택적, 없으면 현재 시점)
+    pub zookie: Option<String>,
+}
+
+/// Watch 이벤트 (튜플 변경 하나)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchEvent {
+    /// 작업 타입
+    pub operation: Operation,
+    /// 변경된 튜플 (created_at은 변경 시간)
+    pub tuple: ApiRelationTuple,
+    /// 변경 번호 (네임스페이스 안에서 단조 증가)
+    pub revision: i64,
+    /// 변경 시점의 일관성 토큰 (재연결 시 zookie로 사용)
+    pub zookie: String,
+}
+
+/// 인덱스 정합성 검사 요청 (쿼리 파라미터)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconcileRequest {
+    /// true면 복구 없이 검사만 수행 (선택적, 기본값 false)
+    pub dry_run: Option<bool>,
+}
+
+/// 인덱스 테이블 불일치 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiscrepancyKind {
+    /// relation_tuples에 있지만 인덱스 테이블에 없음
+    Missing,
+    /// 인덱스 테이블에 있지만 relation_tuples에 없음
+    Orphaned,
+}
+
+/// 인덱스 테이블 불일치 하나
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Discrepancy {
+    /// 불일치가 발견된 인덱스 테이블
+    pub table: String,
+    pub kind: DiscrepancyKind,
+    pub tuple: ApiRelationTuple,
+}
+
+/// 인덱스 테이블 정합성 검사 결과
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReconcileReport {
+    /// 검사한 relation_tuples 행 수
+    pub scanned_tuples: u64,
+    /// 검사한 인덱스 테이블 행 수
+    pub scanned_index_rows: u64,
+    /// 인덱스 테이블에 빠진 행 수
+    pub missing: u64,
+    /// 원본 없이 남은 인덱스 행 수
+    pub orphaned: u64,
+    /// 복구한 행 수 (dry_run이면 0)
+    pub repaired: u64,
+    /// 복구 없이 검사만 했는지
+    pub dry_run: bool,
+    /// 발견한 불일치 (최대 MAX_REPORTED_DISCREPANCIES개)
+    pub discrepancies: Vec<Discrepancy>,
+}
+
+/// 보고서에 담는 최대 불일치 수
+pub const MAX_REPORTED_DISCREPANCIES: usize = 100;
+
+impl ReconcileReport {
+    /// 불일치 기록 (개수는 모두 세고 상세는 최대 MAX_REPORTED_DISCREPANCIES개까지만 보관)
+    pub fn record(&mut self, table: &str, kind: DiscrepancyKind, tuple: &RelationTuple) {
+        match kind {
+            DiscrepancyKind::Missing => self.missing += 1,
+            DiscrepancyKind::Orphaned => self.orphaned += 1,
+        }
+        if self.discrepancies.len() < MAX_REPORTED_DISCREPANCIES {
+            self.discrepancies.push(Discrepancy {
+                table: table.to_string(),
+                kind,
+                tuple: tuple.to_api_tuple(),
+            });
+        }
+    }
+}
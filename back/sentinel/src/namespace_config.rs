@@ -0,0 +1,489 @@
+use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use crate::errors::{SentinelError, SentinelResult};
+use crate::models::RelationTuple;
+use crate::permission_hierarchy::PermissionHierarchy;
+
+/// 네임스페이스 설정 (namespaces 테이블의 config 컬럼에 JSON으로 저장)
+/// Zanzibar의 namespace configuration과 동일하게 관계별 userset rewrite 규칙을 정의
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NamespaceConfig {
+    /// 네임스페이스 이름 (예: "documents", "teams")
+    pub name: String,
+    /// 네임스페이스에 정의된 관계들
+    #[serde(default)]
+    pub relations: Vec<RelationConfig>,
+    /// 권한 계층 (높은 권한부터, 예: ["owner", "manager", "member"])
+    /// 각 관계는 바로 위 단계의 관계를 가진 주체에게도 허용됨
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hierarchy: Vec<String>,
+}
+
+/// 관계 정의
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RelationConfig {
+    /// 관계 이름 (예: "owner", "viewer", "member")
+    pub name: String,
+    /// userset rewrite 규칙 (없으면 `this` - 직접 저장된 튜플만 사용)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rewrite: Option<UsersetRewrite>,
+    /// 직접 저장할 수 있는 주체 타입 (없으면 모든 주체 허용)
+    /// 예: ["user", "teams#member", "folders"] - 사용자, teams의 member userset, folders 객체
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject_types: Option<Vec<String>>,
+}
+
+/// Zanzibar userset rewrite 규칙
+///
+/// JSON 예시:
+/// `{"union": ["this", {"computed_userset": {"relation": "editor"}}]}`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UsersetRewrite {
+    /// 해당 관계에 직접 저장된 튜플 (userset 튜플 포함)
+    This,
+    /// 같은 객체의 다른 관계 (예: editor는 viewer를 포함)
+    ComputedUserset {
+        relation: String,
+    },
+    /// tupleset 관계로 연결된 객체의 관계 (예: document#parent -> folder#viewer)
+    TupleToUserset {
+        tupleset: String,
+        computed_userset: String,
+    },
+    /// 하위 규칙 중 하나라도 만족
+    Union(Vec<UsersetRewrite>),
+    /// 하위 규칙을 모두 만족
+    Intersection(Vec<UsersetRewrite>),
+    /// base는 만족하고 subtract는 만족하지 않음
+    Exclusion {
+        base: Box<UsersetRewrite>,
+        subtract: Box<UsersetRewrite>,
+    },
+}
+
+impl UsersetRewrite {
+    /// 규칙이 직접 저장된 튜플(`this`)을 참조하는지 확인
+    pub fn includes_this(&self) -> bool {
+        match self {
+            UsersetRewrite::This => true,
+            UsersetRewrite::ComputedUserset { .. } | UsersetRewrite::TupleToUserset { .. } => false,
+            UsersetRewrite::Union(children) | UsersetRewrite::Intersection(children) => {
+                children.iter().any(UsersetRewrite::includes_this)
+            }
+            UsersetRewrite::Exclusion { base, subtract } => base.includes_this() || subtract.includes_this(),
+        }
+    }
+}
+
+/// rewrite가 정의되지 않은 관계에 사용하는 기본 규칙
+static THIS: UsersetRewrite = UsersetRewrite::This;
+
+/// 네임스페이스 설정이 없을 때 사용하는 기본 권한 계층 (높은 권한부터)
+const LEGACY_RELATIONS: [&str; 5] = ["owner", "admin", "editor", "commenter", "viewer"];
+
+/// 서버 시작 시 등록되어 있지 않으면 자동으로 등록하는 기본 네임스페이스
+/// teams: Team 서비스가 사용하는 owner > admin > member 계층
+const BUILTIN_NAMESPACES: [&str; 1] = [r#"{
+    "name": "teams",
+    "hierarchy": ["owner", "admin", "member"],
+    "relations": [
+        {"name": "owner", "subject_types": ["user"]},
+        {"name": "admin", "subject_types": ["user"]},
+        {"name": "member", "subject_types": ["user"]}
+    ]
+}"#];
+
+impl NamespaceConfig {
+    /// 네임스페이스 설정이 등록되지 않은 경우의 기본 설정
+    /// owner > admin > editor > commenter > viewer 계층
+    pub fn legacy(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            relations: Vec::new(),
+            hierarchy: LEGACY_RELATIONS.iter().map(|r| r.to_string()).collect(),
+        }
+    }
+
+    /// 기본 네임스페이스 설정 목록
+    pub fn builtin() -> Vec<Self> {
+        BUILTIN_NAMESPACES
+            .iter()
+            .map(|json| Self::from_json(json).expect("invalid builtin namespace config"))
+            .collect()
+    }
+
+    /// JSON 문자열에서 설정 파싱 및 검증
+    pub fn from_json(json: &str) -> SentinelResult<Self> {
+        let config: Self = serde_json::from_str(json)
+            .map_err(|e| SentinelError::validation_error(format!("Invalid namespace config: {}", e)))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// JSON 문자열로 직렬화
+    pub fn to_json(&self) -> SentinelResult<String> {
+        serde_json::to_string(self)
+            .map_err(|e| SentinelError::internal_error(format!("Failed to serialize namespace config: {}", e)))
+    }
+
+    /// 관계 정의 조회
+    pub fn relation(&self, relation: &str) -> Option<&RelationConfig> {
+        self.relations.iter().find(|r| r.name == relation)
+    }
+
+    /// 선언된 관계인지 확인 (relations 또는 hierarchy에 포함)
+    pub fn is_declared(&self, relation: &str) -> bool {
+        self.relation(relation).is_some() || self.hierarchy.iter().any(|r| r == relation)
+    }
+
+    /// 선언된 모든 관계 이름 (relations 순서 다음에 hierarchy에만 있는 관계)
+    pub fn relation_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.relations.iter().map(|r| r.name.as_str()).collect();
+        for relation in &self.hierarchy {
+            if !names.contains(&relation.as_str()) {
+                names.push(relation);
+            }
+        }
+        names
+    }
+
+    /// 설정의 hierarchy로 권한 계층 생성
+    pub fn permission_hierarchy(&self) -> PermissionHierarchy {
+        PermissionHierarchy::from_ordered(&self.hierarchy)
+    }
+
+    /// 관계에 직접 튜플을 저장할 수 있는지 확인 (선언되어 있고 rewrite에 `this` 포함)
+    pub fn accepts_direct_tuples(&self, relation: &str) -> bool {
+        self.is_declared(relation) && self.rewrite_for(relation).includes_this()
+    }
+
+    /// 관계의 rewrite 규칙 조회
+    /// 정의되지 않은 관계나 rewrite가 없는 관계는 `this`로 처리
+    pub fn rewrite_for(&self, relation: &str) -> &UsersetRewrite {
+        self.relation(relation)
+            .and_then(|r| r.rewrite.as_ref())
+            .unwrap_or(&THIS)
+    }
+
+    /// 직접 저장하려는 튜플이 설정에 맞는지 확인
+    /// 맞지 않으면 (거부된 필드, 사유) 반환
+    pub fn tuple_violation(&self, tuple: &RelationTuple) -> Option<(&'static str, String)> {
+        if !self.is_declared(&tuple.relation) {
+            return Some(("relation", format!(
+                "Relation '{}' is not declared in namespace '{}'", tuple.relation, self.name
+            )));
+        }
+        if !self.rewrite_for(&tuple.relation).includes_this() {
+            return Some(("relation", format!(
+                "Relation '{}#{}' is computed and does not accept direct tuples", self.name, tuple.relation
+            )));
+        }
+
+        let Some(subject_type) = tuple.subject_type() else {
+            return Some(("user_id", format!(
+                "Userset subject '{}' must have the form namespace:object_id#relation", tuple.user_id
+            )));
+        };
+        let allowed = self.relation(&tuple.relation).and_then(|r| r.subject_types.as_ref());
+        if let Some(allowed) = allowed
+            && !allowed.contains(&subject_type)
+        {
+            return Some(("user_type", format!(
+                "Relation '{}#{}' does not allow subject type '{}' (allowed: {})",
+                self.name, tuple.relation, subject_type, allowed.join(", ")
+            )));
+        }
+
+        None
+    }
+
+    /// 설정 검증 (관계 이름 중복, 정의되지 않은 관계 참조 등)
+    pub fn validate(&self) -> SentinelResult<()> {
+        if self.name.is_empty() {
+            return Err(SentinelError::validation_error("Namespace name must not be empty"));
+        }
+
+        let mut names = HashSet::new();
+        for relation in &self.relations {
+            if relation.name.is_empty() {
+                return Err(SentinelError::validation_error(format!(
+                    "Namespace '{}' has a relation with an empty name", self.name
+                )));
+            }
+            if !names.insert(relation.name.as_str()) {
+                return Err(SentinelError::validation_error(format!(
+                    "Namespace '{}' defines relation '{}' more than once", self.name, relation.name
+                )));
+            }
+        }
+
+        let mut hierarchy = HashSet::new();
+        for relation in &self.hierarchy {
+            if relation.is_empty() || !hierarchy.insert(relation.as_str()) {
+                return Err(SentinelError::validation_error(format!(
+                    "Namespace '{}' has an empty or repeated hierarchy entry '{}'", self.name, relation
+                )));
+            }
+        }
+        names.extend(hierarchy);
+
+        if names.is_empty() {
+            return Err(SentinelError::validation_error(format!(
+                "Namespace '{}' declares no relations", self.name
+            )));
+        }
+
+        for relation in &self.relations {
+            if let Some(rewrite) = &relation.rewrite {
+                self.validate_rewrite(&relation.name, rewrite, &names)?;
+            }
+            if let Some(subject_types) = &relation.subject_types {
+                self.validate_subject_types(&relation.name, subject_types, &names)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// subject_types 항목 형식 확인 ("user", "user:*", "folders", "teams#member")
+    /// 같은 네임스페이스의 userset은 정의된 관계만 허용
+    fn validate_subject_types(
+        &self,
+        relation: &str,
+        subject_types: &[String],
+        names: &HashSet<&str>,
+    ) -> SentinelResult<()> {
+        if subject_types.is_empty() {
+            return Err(SentinelError::validation_error(format!(
+                "Relation '{}#{}' has an empty subject_types list", self.name, relation
+            )));
+        }
+
+        for subject_type in subject_types {
+            // "user:*"는 해당 타입의 와일드카드 주체 (@user:*)
+            let valid = match (subject_type.strip_suffix(":*"), subject_type.split_once('#')) {
+                (Some(wildcard_type), _) => !wildcard_type.is_empty() && !wildcard_type.contains([':', '#']),
+                (None, Some((namespace, userset_relation))) => {
+                    !namespace.is_empty()
+                        && !userset_relation.is_empty()
+                        && (namespace != self.name || names.contains(userset_relation))
+                        && !subject_type.contains(':')
+                }
+                (None, None) => !subject_type.is_empty() && !subject_type.contains(':'),
+            };
+            if !valid {
+                return Err(SentinelError::validation_error(format!(
+                    "Relation '{}#{}' has an invalid subject type '{}'", self.name, relation, subject_type
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// rewrite 규칙이 같은 네임스페이스의 정의된 관계만 참조하는지 확인
+    fn validate_rewrite(
+        &self,
+        relation: &str,
+        rewrite: &UsersetRewrite,
+        names: &HashSet<&str>,
+    ) -> SentinelResult<()> {
+        let undefined = |referenced: &str| {
+            SentinelError::validation_error(format!(
+                "Relation '{}#{}' references undefined relation '{}'",
+                self.name, relation, referenced
+            ))
+        };
+
+        match rewrite {
+            UsersetRewrite::This => Ok(()),
+            UsersetRewrite::ComputedUserset { relation: computed } => {
+                if names.contains(computed.as_str()) {
+                    Ok(())
+                } else {
+                    Err(undefined(computed))
+                }
+            }
+            // computed_userset은 tupleset이 가리키는 다른 네임스페이스의 관계이므로 여기서 검증하지 않음
+            UsersetRewrite::TupleToUserset { tupleset, .. } => {
+                if names.contains(tupleset.as_str()) {
+                    Ok(())
+                } else {
+                    Err(undefined(tupleset))
+                }
+            }
+            UsersetRewrite::Union(children) | UsersetRewrite::Intersection(children) => {
+                if children.is_empty() {
+                    return Err(SentinelError::validation_error(format!(
+                        "Relation '{}#{}' has an empty union/intersection", self.name, relation
+                    )));
+                }
+                for child in children {
+                    self.validate_rewrite(relation, child, names)?;
+                }
+                Ok(())
+            }
+            UsersetRewrite::Exclusion { base, subtract } => {
+                self.validate_rewrite(relation, base, names)?;
+                self.validate_rewrite(relation, subtract, names)
+            }
+        }
+    }
+}
+
+/// 레지스트리에 보관되는 네임스페이스 (설정 + 설정에서 만든 권한 계층)
+#[derive(Debug, Clone)]
+pub struct NamespaceSchema {
+    /// 네임스페이스 설정
+    pub config: NamespaceConfig,
+    /// 네임스페이스 권한 계층
+    pub hierarchy: PermissionHierarchy,
+    /// 저장소에 등록된 설정인지 여부 (false면 기본 계층으로 대체된 네임스페이스)
+    pub registered: bool,
+}
+
+impl NamespaceSchema {
+    /// 등록된 설정으로부터 NamespaceSchema 생성
+    pub fn new(config: NamespaceConfig) -> Self {
+        let hierarchy = config.permission_hierarchy();
+        Self { config, hierarchy, registered: true }
+    }
+
+    /// 등록되지 않은 네임스페이스용 기본 계층 NamespaceSchema 생성
+    pub fn unregistered(name: &str) -> Self {
+        let config = NamespaceConfig::legacy(name);
+        let hierarchy = config.permission_hierarchy();
+        Self { config, hierarchy, registered: false }
+    }
+
+    /// 네임스페이스 이름
+    pub fn name(&self) -> &str {
+        &self.config.name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_namespace_config() {
+        let json = r#"{
+            "name": "documents",
+            "relations": [
+                {"name": "owner"},
+                {"name": "parent"},
+                {"name": "banned"},
+                {"name": "editor", "rewrite": {"union": ["this", {"computed_userset": {"relation": "owner"}}]}},
+                {"name": "viewer", "rewrite": {"exclusion": {
+                    "base": {"union": [
+                        "this",
+                        {"computed_userset": {"relation": "editor"}},
+                        {"tuple_to_userset": {"tupleset": "parent", "computed_userset": "viewer"}}
+                    ]},
+                    "subtract": {"computed_userset": {"relation": "banned"}}
+                }}}
+            ]
+        }"#;
+
+        let config = NamespaceConfig::from_json(json).unwrap();
+        assert_eq!(config.relations.len(), 5);
+        assert_eq!(config.rewrite_for("owner"), &UsersetRewrite::This);
+        assert_eq!(config.rewrite_for("undefined"), &UsersetRewrite::This);
+        assert!(matches!(config.rewrite_for("viewer"), UsersetRewrite::Exclusion { .. }));
+
+        let reparsed = NamespaceConfig::from_json(&config.to_json().unwrap()).unwrap();
+        assert_eq!(config, reparsed);
+    }
+
+    #[test]
+    fn test_invalid_namespace_config() {
+        // 정의되지 않은 관계 참조
+        let json = r#"{"name": "teams", "relations": [
+            {"name": "member", "rewrite": {"computed_userset": {"relation": "onwer"}}}
+        ]}"#;
+        assert!(NamespaceConfig::from_json(json).is_err());
+
+        // 중복된 관계
+        let json = r#"{"name": "teams", "relations": [{"name": "member"}, {"name": "member"}]}"#;
+        assert!(NamespaceConfig::from_json(json).is_err());
+
+        // 빈 union
+        let json = r#"{"name": "teams", "relations": [{"name": "member", "rewrite": {"union": []}}]}"#;
+        assert!(NamespaceConfig::from_json(json).is_err());
+    }
+
+    #[test]
+    fn test_hierarchy_config() {
+        let json = r#"{"name": "teams", "hierarchy": ["owner", "manager", "member"]}"#;
+        let config = NamespaceConfig::from_json(json).unwrap();
+        assert!(config.is_declared("manager"));
+        assert!(!config.is_declared("viewer"));
+        assert_eq!(config.relation_names(), vec!["owner", "manager", "member"]);
+
+        let hierarchy = config.permission_hierarchy();
+        assert_eq!(hierarchy.get_level("member"), 1);
+        assert_eq!(hierarchy.next_higher("member"), Some("manager"));
+
+        // 계층 관계도 rewrite에서 참조 가능
+        let json = r#"{"name": "projects", "hierarchy": ["owner", "maintainer", "reporter"], "relations": [
+            {"name": "auditor", "rewrite": {"union": ["this", {"computed_userset": {"relation": "maintainer"}}]}}
+        ]}"#;
+        assert!(NamespaceConfig::from_json(json).is_ok());
+
+        let json = r#"{"name": "teams", "hierarchy": ["owner", "owner"]}"#;
+        assert!(NamespaceConfig::from_json(json).is_err());
+
+        let json = r#"{"name": "teams"}"#;
+        assert!(NamespaceConfig::from_json(json).is_err());
+    }
+
+    #[test]
+    fn test_legacy_config() {
+        let config = NamespaceConfig::legacy("documents");
+        assert!(config.validate().is_ok());
+        assert_eq!(config.rewrite_for("viewer"), &UsersetRewrite::This);
+        assert_eq!(config.permission_hierarchy().get_level("owner"), 5);
+        assert_eq!(config.permission_hierarchy().next_higher("viewer"), Some("commenter"));
+    }
+
+    #[test]
+    fn test_tuple_violation() {
+        let json = r#"{"name": "documents", "relations": [
+            {"name": "owner", "subject_types": ["user"]},
+            {"name": "parent", "subject_types": ["folders"]},
+            {"name": "editor", "subject_types": ["user", "teams#member"]},
+            {"name": "viewer", "rewrite": {"computed_userset": {"relation": "editor"}}},
+            {"name": "reader", "subject_types": ["user", "user:*"]}
+        ]}"#;
+        let config = NamespaceConfig::from_json(json).unwrap();
+        let tuple = |relation: &str, user_type: &str, user_id: &str| {
+            RelationTuple::new("documents", "doc1", relation, user_type, user_id)
+        };
+
+        assert_eq!(config.tuple_violation(&tuple("owner", "user", "alice")), None);
+        assert_eq!(config.tuple_violation(&tuple("parent", "folders", "f1")), None);
+        assert_eq!(config.tuple_violation(&tuple("editor", "userset", "teams:backend#member")), None);
+
+        let field = |t: RelationTuple| config.tuple_violation(&t).map(|(field, _)| field);
+        assert_eq!(field(tuple("onwer", "user", "alice")), Some("relation"));
+        assert_eq!(field(tuple("viewer", "user", "alice")), Some("relation"));
+        assert_eq!(field(tuple("owner", "usr", "alice")), Some("user_type"));
+        assert_eq!(field(tuple("editor", "userset", "teams:backend#owner")), Some("user_type"));
+        assert_eq!(field(tuple("editor", "userset", "teams:backend")), Some("user_id"));
+        // 와일드카드 주체는 subject_types에 "user:*"가 있어야 허용
+        assert_eq!(config.tuple_violation(&tuple("reader", "user", "*")), None);
+        assert_eq!(field(tuple("owner", "user", "*")), Some("user_type"));
+
+        // 잘못된 subject_types 설정
+        let json = r#"{"name": "documents", "relations": [{"name": "owner", "subject_types": []}]}"#;
+        assert!(NamespaceConfig::from_json(json).is_err());
+        let json = r#"{"name": "documents", "relations": [{"name": "owner", "subject_types": ["documents#onwer"]}]}"#;
+        assert!(NamespaceConfig::from_json(json).is_err());
+        let json = r#"{"name": "documents", "relations": [{"name": "owner", "subject_types": ["teams#member:*"]}]}"#;
+        assert!(NamespaceConfig::from_json(json).is_err());
+
+        assert!(NamespaceConfig::builtin().iter().any(|config| config.name == "teams"));
+    }
+}
@@ -0,0 +1,717 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use scylla::client::session::Session;
+use scylla::response::query_result::QueryResult;
+use scylla::value::{CqlTimestamp, CqlValue, Row};
+use tracing::{info, warn};
+use crate::cache::Cache;
+use crate::permission_checker::PermissionChecker;
+use crate::models::{NamespaceRecord, Operation, TupleUpdate};
+use crate::namespace_config::{NamespaceConfig, NamespaceSchema};
+use crate::caveat::Caveat;
+use crate::tuple_store::TupleStore;
+use crate::errors::{SentinelError, SentinelResult, Violation};
+
+/// 쓰기 요청 하나에 담을 수 있는 최대 업데이트 수
+/// 요청 전체가 하나의 logged batch로 반영되므로 batch 크기 제한을 넘지 않도록 제한
+pub const MAX_WRITE_UPDATES: usize = 500;
+
+/// 네임스페이스 설정 저장소 trait
+/// namespaces 테이블의 config(JSON)와 버전 이력을 읽고 쓰는 역할
+#[async_trait::async_trait]
+pub trait NamespaceStore: Send + Sync {
+    /// 네임스페이스 설정 조회
+    async fn find_namespace(&self, name: &str) -> SentinelResult<Option<NamespaceRecord>>;
+
+    /// 등록된 모든 네임스페이스 설정 조회
+    async fn list_namespaces(&self) -> SentinelResult<Vec<NamespaceRecord>>;
+
+    /// 특정 버전의 네임스페이스 설정 조회
+    async fn find_namespace_version(&self, name: &str, version: i32) -> SentinelResult<Option<NamespaceRecord>>;
+
+    /// 네임스페이스 등록 (이미 존재하면 ConflictError)
+    async fn create_namespace(&self, config: &NamespaceConfig) -> SentinelResult<()>;
+
+    /// 네임스페이스 설정 수정 (현재 버전이 current_version이 아니면 ConflictError)
+    async fn update_namespace(&self, config: &NamespaceConfig, current_version: i32) -> SentinelResult<()>;
+
+    /// 네임스페이스 삭제 (삭제된 경우 true)
+    async fn delete_namespace(&self, name: &str) -> SentinelResult<bool>;
+}
+
+/// namespaces 테이블 조회 결과 (name, config, version, created_at, updated_at)
+type NamespaceRow = (String, Option<String>, Option<i32>, Option<CqlTimestamp>, Option<CqlTimestamp>);
+
+/// ScyllaDB 기반 NamespaceStore 구현체
+pub struct ScyllaNamespaceStore {
+    session: Arc<Session>,
+}
+
+impl ScyllaNamespaceStore {
+    pub fn new(session: Arc<Session>) -> Self {
+        Self { session }
+    }
+
+    /// 조회 결과 행을 NamespaceRecord로 변환 (잘못된 설정은 경고 후 무시)
+    fn to_record(row: NamespaceRow) -> Option<NamespaceRecord> {
+        let (name, config, version, created_at, updated_at) = row;
+        let config = match NamespaceConfig::from_json(config.as_deref()?) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                warn!("Ignoring invalid config for namespace {}: {}", name, e);
+                return None;
+            }
+        };
+
+        let created_at = to_datetime(created_at);
+        Some(NamespaceRecord {
+            config,
+            version: version.unwrap_or(1),
+            created_at,
+            updated_at: updated_at.map(|t| to_datetime(Some(t))).unwrap_or(created_at),
+        })
+    }
+
+    /// 조회 결과의 모든 행을 NamespaceRecord로 변환
+    fn to_records(result: QueryResult) -> SentinelResult<Vec<NamespaceRecord>> {
+        let rows = result.into_rows_result()
+            .map_err(|e| SentinelError::internal_error(format!("Query result error: {}", e)))?;
+
+        let mut records = Vec::new();
+        for row in rows.rows::<NamespaceRow>()
+            .map_err(|e| SentinelError::from_rows_error(e, "Failed to access rows"))? {
+            let row = row
+                .map_err(|e| SentinelError::internal_error(format!("Row parsing error: {}", e)))?;
+            records.extend(Self::to_record(row));
+        }
+
+        Ok(records)
+    }
+
+    /// 버전 이력 테이블에 설정 기록
+    async fn record_version(&self, config: &NamespaceConfig, version: i32, now: CqlTimestamp) -> SentinelResult<()> {
+        let query = "
+            INSERT INTO sentinel.namespace_versions (name, version, config, created_at)
+            VALUES (?, ?, ?, ?)
+        ";
+
+        self.session
+            .query_unpaged(query, (&config.name, version, config.to_json()?, now))
+            .await
+            .map_err(|e| SentinelError::from_scylla_error(e, "Failed to record namespace version"))?;
+
+        Ok(())
+    }
+}
+
+/// CqlTimestamp를 DateTime으로 변환
+fn to_datetime(timestamp: Option<CqlTimestamp>) -> DateTime<Utc> {
+    timestamp
+        .and_then(|t| DateTime::from_timestamp_millis(t.0))
+        .unwrap_or_else(Utc::now)
+}
+
+/// LWT(IF ...) 쿼리 결과의 [applied] 컬럼 확인
+pub(crate) fn lwt_applied(result: QueryResult) -> SentinelResult<bool> {
+    let rows = result.into_rows_result()
+        .map_err(|e| SentinelError::internal_error(format!("Query result error: {}", e)))?;
+
+    let row: Option<Row> = rows.maybe_first_row()
+        .map_err(|e| SentinelError::internal_error(format!("Row parsing error: {}", e)))?;
+
+    match row.as_ref().and_then(|r| r.columns.first()) {
+        Some(Some(CqlValue::Boolean(applied))) => Ok(*applied),
+        _ => Err(SentinelError::internal_error("Missing [applied] column in LWT result")),
+    }
+}
+
+#[async_trait::async_trait]
+impl NamespaceStore for ScyllaNamespaceStore {
+    /// 네임스페이스 설정 조회
+    async fn find_namespace(&self, name: &str) -> SentinelResult<Option<NamespaceRecord>> {
+        let query = "
+            SELECT name, config, version, created_at, updated_at
+            FROM sentinel.namespaces
+            WHERE name = ?
+        ";
+
+        let result = self.session
+            .query_unpaged(query, (name,))
+            .await
+            .map_err(|e| SentinelError::from_scylla_error(e, "Failed to find namespace"))?;
+
+        Ok(Self::to_records(result)?.into_iter().next())
+    }
+
+    /// 등록된 모든 네임스페이스 설정 조회
+    async fn list_namespaces(&self) -> SentinelResult<Vec<NamespaceRecord>> {
+        let query = "
+            SELECT name, config, version, created_at, updated_at
+            FROM sentinel.namespaces
+        ";
+
+        let result = self.session
+            .query_unpaged(query, &[])
+            .await
+            .map_err(|e| SentinelError::from_scylla_error(e, "Failed to list namespaces"))?;
+
+        Self::to_records(result)
+    }
+
+    /// 특정 버전의 네임스페이스 설정 조회
+    async fn find_namespace_version(&self, name: &str, version: i32) -> SentinelResult<Option<NamespaceRecord>> {
+        let query = "
+            SELECT name, config, version, created_at, created_at
+            FROM sentinel.namespace_versions
+            WHERE name = ? AND version = ?
+        ";
+
+        let result = self.session
+            .query_unpaged(query, (name, version))
+            .await
+            .map_err(|e| SentinelError::from_scylla_error(e, "Failed to find namespace version"))?;
+
+        Ok(Self::to_records(result)?.into_iter().next())
+    }
+
+    /// 네임스페이스 등록 (LWT로 중복 등록 방지)
+    async fn create_namespace(&self, config: &NamespaceConfig) -> SentinelResult<()> {
+        let query = "
+            INSERT INTO sentinel.namespaces (name, config, version, created_at, updated_at)
+            VALUES (?, ?, 1, ?, ?)
+            IF NOT EXISTS
+        ";
+
+        let now = CqlTimestamp(Utc::now().timestamp_millis());
+        let result = self.session
+            .query_unpaged(query, (&config.name, config.to_json()?, now, now))
+            .await
+            .map_err(|e| SentinelError::from_scylla_error(e, "Failed to create namespace"))?;
+
+        if !lwt_applied(result)? {
+            return Err(SentinelError::conflict_error(format!(
+                "Namespace '{}' already exists", config.name
+            )));
+        }
+
+        self.record_version(config, 1, now).await
+    }
+
+    /// 네임스페이스 설정 수정 (LWT로 버전 확인)
+    async fn update_namespace(&self, config: &NamespaceConfig, current_version: i32) -> SentinelResult<()> {
+        let query = "
+            UPDATE sentinel.namespaces
+            SET config = ?, version = ?, updated_at = ?
+            WHERE name = ?
+            IF version = ?
+        ";
+
+        let now = CqlTimestamp(Utc::now().timestamp_millis());
+        let next_version = current_version + 1;
+        let result = self.session
+            .query_unpaged(query, (config.to_json()?, next_version, now, &config.name, current_version))
+            .await
+            .map_err(|e| SentinelError::from_scylla_error(e, "Failed to update namespace"))?;
+
+        if !lwt_applied(result)? {
+            return Err(SentinelError::conflict_error(format!(
+                "Namespace '{}' was modified concurrently (expected version {})",
+                config.name, current_version
+            )));
+        }
+
+        self.record_version(config, next_version, now).await
+    }
+
+    /// 네임스페이스 삭제 (버전 이력 포함)
+    async fn delete_namespace(&self, name: &str) -> SentinelResult<bool> {
+        let query = "
+            DELETE FROM sentinel.namespaces
+            WHERE name = ?
+            IF EXISTS
+        ";
+
+        let result = self.session
+            .query_unpaged(query, (name,))
+            .await
+            .map_err(|e| SentinelError::from_scylla_error(e, "Failed to delete namespace"))?;
+
+        if !lwt_applied(result)? {
+            return Ok(false);
+        }
+
+        let history_query = "
+            DELETE FROM sentinel.namespace_versions
+            WHERE name = ?
+        ";
+
+        self.session
+            .query_unpaged(history_query, (name,))
+            .await
+            .map_err(|e| SentinelError::from_scylla_error(e, "Failed to delete namespace versions"))?;
+
+        Ok(true)
+    }
+}
+
+/// 네임스페이스에 튜플이 저장된 관계를 새 설정이 모두 직접 튜플로 받는지 확인
+/// (관계 삭제, rewrite에서 `this` 제거, 설정에 없는 관계의 튜플이 있으면 기존 튜플이 고아가 됨)
+/// updated가 None이면 네임스페이스 삭제이므로 튜플이 하나라도 있으면 거부
+async fn ensure_no_orphaned_tuples(
+    tuple_store: &dyn TupleStore,
+    namespace: &str,
+    updated: Option<&NamespaceConfig>,
+) -> SentinelResult<()> {
+    let orphaned: Vec<String> = tuple_store.stored_relations(namespace).await?
+        .into_iter()
+        .filter(|relation| !updated.is_some_and(|config| config.accepts_direct_tuples(relation)))
+        .collect();
+
+    if orphaned.is_empty() {
+        Ok(())
+    } else {
+        Err(SentinelError::validation_error(format!(
+            "Namespace '{}' still has tuples for relations that would no longer accept them: {}",
+            namespace,
+            orphaned.join(", ")
+        )))
+    }
+}
+
+/// 네임스페이스 설정 레지스트리
+/// 저장소에서 읽은 설정을 메모리에 보관하고, 등록되지 않은 네임스페이스는 기본 계층을 사용
+/// 설정 변경은 `reload`로 반영 (main에서 주기적으로 호출)
+pub struct NamespaceRegistry {
+    store: Arc<dyn NamespaceStore>,
+    schemas: RwLock<HashMap<String, Arc<NamespaceSchema>>>,
+}
+
+impl NamespaceRegistry {
+    /// 새로운 NamespaceRegistry 생성
+    pub fn new(store: Arc<dyn NamespaceStore>) -> Self {
+        Self {
+            store,
+            schemas: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// 네임스페이스 조회 (메모리 → 저장소 → 기본 설정 순)
+    /// 기본 설정은 메모리에 보관하지 않음 (요청이 보낸 임의의 네임스페이스 이름으로 메모리가 늘지 않도록)
+    pub async fn get(&self, namespace: &str) -> SentinelResult<Arc<NamespaceSchema>> {
+        if let Some(schema) = self.read_schemas().get(namespace) {
+            return Ok(schema.clone());
+        }
+
+        let Some(record) = self.store.find_namespace(namespace).await? else {
+            return Ok(Arc::new(NamespaceSchema::unregistered(namespace)));
+        };
+        info!("Loaded namespace config: {} (version {})", namespace, record.version);
+        let schema = Arc::new(NamespaceSchema::new(record.config));
+        self.write_schemas().insert(namespace.to_string(), schema.clone());
+        Ok(schema)
+    }
+
+    /// 저장소에서 모든 설정을 다시 읽어 메모리 설정 교체
+    /// 변경(추가/수정/삭제)된 네임스페이스 이름 목록 반환
+    pub async fn reload(&self) -> SentinelResult<Vec<String>> {
+        let configs: Vec<NamespaceConfig> = self.store
+            .list_namespaces()
+            .await?
+            .into_iter()
+            .map(|record| record.config)
+            .collect();
+        let mut schemas = self.write_schemas();
+        let mut changed = Vec::new();
+
+        for config in &configs {
+            let unchanged = schemas
+                .get(&config.name)
+                .is_some_and(|schema| schema.registered && schema.config == *config);
+            if !unchanged {
+                info!("Namespace config changed: {}", config.name);
+                changed.push(config.name.clone());
+                schemas.insert(config.name.clone(), Arc::new(NamespaceSchema::new(config.clone())));
+            }
+        }
+
+        // 저장소에서 삭제된 네임스페이스는 기본 설정으로 되돌림
+        let removed: Vec<String> = schemas
+            .iter()
+            .filter(|(name, schema)| {
+                schema.registered && !configs.iter().any(|config| &config.name == *name)
+            })
+            .map(|(name, _)| name.clone())
+            .collect();
+        for name in removed {
+            info!("Namespace config removed: {}", name);
+            schemas.remove(&name);
+            changed.push(name);
+        }
+
+        Ok(changed)
+    }
+
+    /// 저장된 네임스페이스 설정 조회
+    pub async fn find_record(&self, name: &str) -> SentinelResult<Option<NamespaceRecord>> {
+        self.store.find_namespace(name).await
+    }
+
+    /// 특정 버전의 네임스페이스 설정 조회
+    pub async fn find_record_version(&self, name: &str, version: i32) -> SentinelResult<Option<NamespaceRecord>> {
+        self.store.find_namespace_version(name, version).await
+    }
+
+    /// 등록된 모든 네임스페이스 설정 조회
+    pub async fn list_records(&self) -> SentinelResult<Vec<NamespaceRecord>> {
+        self.store.list_namespaces().await
+    }
+
+    /// 네임스페이스 등록
+    /// 설정 없이 이미 저장된 튜플이 새 설정에서 고아가 되지 않는지 확인
+    pub async fn create(&self, config: NamespaceConfig, tuple_store: &dyn TupleStore) -> SentinelResult<NamespaceRecord> {
+        config.validate()?;
+        ensure_no_orphaned_tuples(tuple_store, &config.name, Some(&config)).await?;
+
+        self.store.create_namespace(&config).await?;
+        info!("Namespace registered: {}", config.name);
+        self.refresh(&config.name).await
+    }
+
+    /// 네임스페이스 설정 수정 (버전 증가)
+    /// expected_version이 주어지면 현재 버전과 같아야 함
+    pub async fn update(
+        &self,
+        config: NamespaceConfig,
+        expected_version: Option<i32>,
+        tuple_store: &dyn TupleStore,
+    ) -> SentinelResult<NamespaceRecord> {
+        config.validate()?;
+        let current = self.require_record(&config.name).await?;
+        if let Some(expected) = expected_version
+            && expected != current.version
+        {
+            return Err(SentinelError::conflict_error(format!(
+                "Namespace '{}' is at version {}, not {}", config.name, current.version, expected
+            )));
+        }
+        ensure_no_orphaned_tuples(tuple_store, &current.config.name, Some(&config)).await?;
+
+        self.store.update_namespace(&config, current.version).await?;
+        info!("Namespace updated: {} (version {})", config.name, current.version + 1);
+        self.refresh(&config.name).await
+    }
+
+    /// 네임스페이스 삭제 (튜플이 남아 있는 관계가 있으면 거부)
+    pub async fn delete(&self, name: &str, tuple_store: &dyn TupleStore) -> SentinelResult<()> {
+        let current = self.require_record(name).await?;
+        ensure_no_orphaned_tuples(tuple_store, &current.config.name, None).await?;
+
+        if !self.store.delete_namespace(name).await? {
+            return Err(SentinelError::not_found_error(format!("Namespace '{}' is not registered", name)));
+        }
+        info!("Namespace deleted: {}", name);
+        self.write_schemas().remove(name);
+        Ok(())
+    }
+
+    /// 기본 네임스페이스 중 등록되지 않은 것을 등록 (서버 시작 시 호출)
+    pub async fn register_builtin(&self) -> SentinelResult<()> {
+        for config in NamespaceConfig::builtin() {
+            if self.store.find_namespace(&config.name).await?.is_some() {
+                continue;
+            }
+            match self.store.create_namespace(&config).await {
+                // 다른 인스턴스가 먼저 등록한 경우
+                Ok(()) | Err(SentinelError::ConflictError { .. }) => {
+                    info!("Builtin namespace registered: {}", config.name);
+                }
+                Err(e) => return Err(e),
+            }
+            self.write_schemas().remove(&config.name);
+        }
+        Ok(())
+    }
+
+    /// 쓰기 요청의 튜플들을 네임스페이스 설정으로 검증 (저장 전에 호출)
+    /// 삽입은 등록된 네임스페이스, 선언된 관계, 허용된 주체 타입만 가능
+    /// 삭제는 설정 이전에 저장된 튜플도 정리할 수 있도록 필드 형식만 확인
+    /// 같은 튜플을 두 번 변경하거나 MAX_WRITE_UPDATES를 넘는 요청은 거부
+    pub async fn validate_updates(&self, updates: &[TupleUpdate]) -> SentinelResult<()> {
+        if updates.len() > MAX_WRITE_UPDATES {
+            return Err(SentinelError::validation_error(format!(
+                "A write may contain at most {} updates, got {}",
+                MAX_WRITE_UPDATES,
+                updates.len(),
+            )));
+        }
+
+        let mut violations = Vec::new();
+        let mut seen = HashSet::new();
+
+        for (index, update) in updates.iter().enumerate() {
+            let tuple = &update.tuple;
+            // 한 번에 반영되므로 같은 튜플을 두 번 변경하면 결과가 모호함
+            let key = (&tuple.namespace, &tuple.object_id, &tuple.relation, &tuple.user_type, &tuple.user_id);
+            if !seen.insert(key) {
+                violations.push(Violation::new(index, "tuple", "Tuple is updated more than once in this write"));
+                continue;
+            }
+            let fields = [
+                ("namespace", &tuple.namespace),
+                ("object_id", &tuple.object_id),
+                ("relation", &tuple.relation),
+                ("user_type", &tuple.user_type),
+                ("user_id", &tuple.user_id),
+            ];
+            if let Some((field, _)) = fields.iter().find(|(_, value)| value.is_empty()) {
+                violations.push(Violation::new(index, *field, format!("{} must not be empty", field)));
+                continue;
+            }
+            if matches!(update.operation, Operation::Delete) {
+                continue;
+            }
+            if tuple.expires_at.is_some_and(|expires_at| expires_at <= Utc::now()) {
+                violations.push(Violation::new(index, "expires_at", "expires_at must be in the future"));
+                continue;
+            }
+            if let Some(Err(message)) = tuple.caveat.as_ref().map(Caveat::validate) {
+                violations.push(Violation::new(index, "caveat", message));
+                continue;
+            }
+
+            let schema = self.get(&tuple.namespace).await?;
+            if !schema.registered {
+                violations.push(Violation::new(
+                    index,
+                    "namespace",
+                    format!("Namespace '{}' is not registered", tuple.namespace),
+                ));
+                continue;
+            }
+            if let Some((field, message)) = schema.config.tuple_violation(&tuple.to_db_tuple()) {
+                violations.push(Violation::new(index, field, message));
+            }
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(SentinelError::validation_errors(
+                format!("{} of {} tuple updates do not match the namespace configs", violations.len(), updates.len()),
+                violations,
+            ))
+        }
+    }
+
+    /// 등록된 설정 조회 (없으면 NotFoundError)
+    async fn require_record(&self, name: &str) -> SentinelResult<NamespaceRecord> {
+        self.store
+            .find_namespace(name)
+            .await?
+            .ok_or_else(|| SentinelError::not_found_error(format!("Namespace '{}' is not registered", name)))
+    }
+
+    /// 저장소의 설정으로 메모리 설정 갱신
+    async fn refresh(&self, name: &str) -> SentinelResult<NamespaceRecord> {
+        let record = self.require_record(name).await?;
+        self.write_schemas().insert(name.to_string(), Arc::new(NamespaceSchema::new(record.config.clone())));
+        Ok(record)
+    }
+
+    /// 주기적으로 설정을 다시 읽는 루프 (hot reload)
+    /// 설정이 바뀌면 모든 권한 체크 캐시를 무효화 (다른 네임스페이스의 체크도 userset이나
+    /// tuple_to_userset으로 바뀐 네임스페이스의 설정을 거쳐 계산되었을 수 있음)
+    pub async fn run_reload_loop<C: Cache>(self: Arc<Self>, checker: PermissionChecker<C>, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+
+            let changed = match self.reload().await {
+                Ok(changed) => changed,
+                Err(e) => {
+                    warn!("Failed to reload namespace configs: {}", e);
+                    continue;
+                }
+            };
+
+            // 무효화 실패는 invalidate_namespace_cache에서 기록
+            if !changed.is_empty() {
+                let _ = checker.invalidate_namespace_cache(&changed.join(", ")).await;
+            }
+        }
+    }
+
+    fn read_schemas(&self) -> std::sync::RwLockReadGuard<'_, HashMap<String, Arc<NamespaceSchema>>> {
+        self.schemas.read().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn write_schemas(&self) -> std::sync::RwLockWriteGuard<'_, HashMap<String, Arc<NamespaceSchema>>> {
+        self.schemas.write().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{parse_tuple, MemoryNamespaceStore, MemoryTupleStore};
+
+    #[tokio::test]
+    async fn test_registry_reload() {
+        let store = Arc::new(MemoryNamespaceStore::from_json(&[
+            r#"{"name": "teams", "hierarchy": ["owner", "member"]}"#,
+        ]));
+        let registry = NamespaceRegistry::new(store.clone());
+
+        // 등록된 설정과 기본 설정
+        let teams = registry.get("teams").await.unwrap();
+        assert_eq!(teams.hierarchy.get_level("member"), 1);
+        let documents = registry.get("documents").await.unwrap();
+        assert_eq!(documents.hierarchy.get_level("owner"), 5);
+        // 등록되지 않은 네임스페이스는 메모리에 남기지 않음
+        assert!(!registry.read_schemas().contains_key("documents"));
+
+        // 변경이 없으면 reload 결과도 비어 있음
+        assert!(registry.reload().await.unwrap().is_empty());
+
+        // 설정 수정과 추가가 반영됨
+        store.put(NamespaceConfig::from_json(r#"{"name": "teams", "hierarchy": ["owner", "manager", "member"]}"#).unwrap());
+        store.put(NamespaceConfig::from_json(r#"{"name": "documents", "hierarchy": ["owner", "viewer"]}"#).unwrap());
+        let mut changed = registry.reload().await.unwrap();
+        changed.sort();
+        assert_eq!(changed, vec!["documents", "teams"]);
+        assert_eq!(registry.get("teams").await.unwrap().hierarchy.next_higher("member"), Some("manager"));
+        assert_eq!(registry.get("documents").await.unwrap().hierarchy.get_level("owner"), 2);
+
+        // 삭제된 설정은 기본 계층으로 되돌아감
+        store.remove("documents");
+        assert_eq!(registry.reload().await.unwrap(), vec!["documents"]);
+        assert_eq!(registry.get("documents").await.unwrap().hierarchy.get_level("owner"), 5);
+    }
+
+    #[tokio::test]
+    async fn test_registry_admin_operations() {
+        let registry = NamespaceRegistry::new(Arc::new(MemoryNamespaceStore::default()));
+        let tuples = MemoryTupleStore::with_tuples(&[
+            "teams:backend#owner@user:alice",
+            "teams:backend#member@user:bob",
+        ]);
+        let config = |json: &str| NamespaceConfig::from_json(json).unwrap();
+
+        // 기본 계층의 owner 튜플이 남아 있으므로 owner가 없는 설정은 등록 불가
+        let without_owner = config(r#"{"name": "teams", "hierarchy": ["manager", "member"]}"#);
+        assert!(registry.create(without_owner, &tuples).await.is_err());
+
+        // 기본 계층에 없는 관계의 튜플도 새 설정에 없으면 등록 불가
+        let reviewers = MemoryTupleStore::with_tuples(&["reports:q3#reviewer@user:carol"]);
+        let without_reviewer = config(r#"{"name": "reports", "hierarchy": ["owner", "viewer"]}"#);
+        assert!(registry.create(without_reviewer, &reviewers).await.is_err());
+        let record = registry
+            .create(config(r#"{"name": "reports", "hierarchy": ["owner", "reviewer", "viewer"]}"#), &reviewers)
+            .await
+            .unwrap();
+        assert_eq!(record.config.name, "reports");
+
+        let record = registry
+            .create(config(r#"{"name": "teams", "hierarchy": ["owner", "member"]}"#), &tuples)
+            .await
+            .unwrap();
+        assert_eq!(record.version, 1);
+        assert!(matches!(
+            registry.create(record.config.clone(), &tuples).await,
+            Err(SentinelError::ConflictError { .. })
+        ));
+
+        // 버전 충돌
+        let with_manager = config(r#"{"name": "teams", "hierarchy": ["owner", "manager", "member"]}"#);
+        assert!(matches!(
+            registry.update(with_manager.clone(), Some(5), &tuples).await,
+            Err(SentinelError::ConflictError { .. })
+        ));
+        let record = registry.update(with_manager, Some(1), &tuples).await.unwrap();
+        assert_eq!(record.version, 2);
+        assert_eq!(registry.get("teams").await.unwrap().hierarchy.get_level("manager"), 2);
+        assert_eq!(registry.find_record_version("teams", 1).await.unwrap().unwrap().config.hierarchy.len(), 2);
+
+        // member 튜플이 고아가 되는 수정은 거부 (관계 삭제, this 제거)
+        let without_member = config(r#"{"name": "teams", "hierarchy": ["owner", "manager"]}"#);
+        assert!(registry.update(without_member, None, &tuples).await.is_err());
+        let computed_member = config(r#"{"name": "teams", "hierarchy": ["owner", "manager"], "relations": [
+            {"name": "member", "rewrite": {"computed_userset": {"relation": "manager"}}}
+        ]}"#);
+        assert!(registry.update(computed_member, None, &tuples).await.is_err());
+
+        // 튜플이 남아 있으면 삭제 불가, 튜플 정리 후 삭제 가능
+        assert!(registry.delete("teams", &tuples).await.is_err());
+        let empty = MemoryTupleStore::new();
+        registry.delete("teams", &empty).await.unwrap();
+        assert!(registry.find_record("teams").await.unwrap().is_none());
+        assert_eq!(registry.get("teams").await.unwrap().hierarchy.get_level("owner"), 5);
+        assert!(matches!(
+            registry.delete("teams", &empty).await,
+            Err(SentinelError::NotFoundError { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_validate_updates() {
+        let registry = NamespaceRegistry::new(Arc::new(MemoryNamespaceStore::default()));
+        registry.register_builtin().await.unwrap();
+        let update = |operation: Operation, tuple: &str| TupleUpdate {
+            operation,
+            tuple: parse_tuple(tuple).to_api_tuple(),
+        };
+
+        registry
+            .validate_updates(&[
+                update(Operation::Insert, "teams:backend#owner@user:1"),
+                update(Operation::Insert, "teams:backend#member@user:2"),
+            ])
+            .await
+            .unwrap();
+
+        // 잘못된 튜플이 하나라도 있으면 전체 거부, 항목별 사유 포함
+        let result = registry
+            .validate_updates(&[
+                update(Operation::Insert, "teams:backend#member@user:2"),
+                update(Operation::Insert, "team:backend#member@user:2"),
+                update(Operation::Insert, "teams:backend#memeber@user:2"),
+                update(Operation::Insert, "teams:backend#member@users:2"),
+                update(Operation::Insert, "teams:#member@user:2"),
+            ])
+            .await;
+        let Err(SentinelError::ValidationError { violations, .. }) = result else {
+            panic!("expected validation error");
+        };
+        let fields: Vec<(usize, &str)> = violations.iter().map(|v| (v.index, v.field.as_str())).collect();
+        assert_eq!(fields, vec![(1, "namespace"), (2, "relation"), (3, "user_type"), (4, "object_id")]);
+
+        // 삭제는 설정 이전의 튜플도 정리할 수 있도록 허용
+        registry
+            .validate_updates(&[update(Operation::Delete, "teams:backend#viewer@user:2")])
+            .await
+            .unwrap();
+
+        // 한 요청 안에서 같은 튜플을 두 번 변경할 수 없음
+        let result = registry
+            .validate_updates(&[
+                update(Operation::Insert, "teams:backend#member@user:2"),
+                update(Operation::Delete, "teams:backend#member@user:2"),
+            ])
+            .await;
+        let Err(SentinelError::ValidationError { violations, .. }) = result else {
+            panic!("expected validation error");
+        };
+        assert_eq!(violations[0].index, 1);
+
+        // 형식이 잘못된 조건은 거부
+        let mut caveated = update(Operation::Touch, "teams:backend#member@user:3");
+        caveated.tuple.caveat = Some(serde_json::from_str(r#"{"ip_in_cidr": {"field": "ip", "cidrs": ["10.0.0.0/99"]}}"#).unwrap());
+        let result = registry.validate_updates(&[caveated]).await;
+        let Err(SentinelError::ValidationError { violations, .. }) = result else {
+            panic!("expected validation error");
+        };
+        assert_eq!(violations[0].field, "caveat");
+    }
+}
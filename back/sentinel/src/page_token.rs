@@ -0,0 +1,141 @@
+use hmac::{Hmac, KeyInit, Mac};
+use sha2::Sha256;
+use tracing::warn;
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use crate::models::RelationTupleFilter;
+use crate::errors::{SentinelError, SentinelResult};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// HMAC-SHA256 서명 길이
+const MAC_LEN: usize = 32;
+
+/// Read API 페이지 토큰 코덱
+/// 토큰은 base64url(저장소 paging state || HMAC(필터 || paging state)) 형식으로,
+/// 클라이언트에게는 불투명하며 변조되거나 다른 필터로 재사용되면 거부됨
+#[derive(Clone)]
+pub struct PageTokenCodec {
+    key: Vec<u8>,
+}
+
+impl PageTokenCodec {
+    /// 주어진 비밀 키로 코덱 생성
+    pub fn new(key: impl Into<Vec<u8>>) -> Self {
+        Self { key: key.into() }
+    }
+
+    /// PAGE_TOKEN_SECRET 환경 변수로 코덱 생성
+    /// 설정되지 않으면 프로세스마다 임의의 키를 사용하므로 재시작하거나 다른 노드로 요청이 가면 토큰이 무효화됨
+    pub fn from_env() -> Self {
+        match std::env::var("PAGE_TOKEN_SECRET") {
+            Ok(secret) if !secret.is_empty() => Self::new(secret),
+            _ => {
+                warn!("PAGE_TOKEN_SECRET is not set; page tokens are only valid on this process");
+                let key = [uuid::Uuid::new_v4().into_bytes(), uuid::Uuid::new_v4().into_bytes()].concat();
+                Self::new(key)
+            }
+        }
+    }
+
+    /// 필터에 묶인 페이지 토큰 생성
+    pub fn encode(&self, filter: &RelationTupleFilter, paging_state: &[u8]) -> String {
+        let mut token = paging_state.to_vec();
+        token.extend_from_slice(&self.sign(filter, paging_state).finalize().into_bytes());
+        URL_SAFE_NO_PAD.encode(token)
+    }
+
+    /// 페이지 토큰 검증 후 저장소 paging state 반환
+    pub fn decode(&self, token: &str, filter: &RelationTupleFilter) -> SentinelResult<Vec<u8>> {
+        let invalid = || SentinelError::validation_error("Invalid page token");
+
+        let mut bytes = URL_SAFE_NO_PAD.decode(token).map_err(|_| invalid())?;
+        if bytes.len() < MAC_LEN {
+            return Err(invalid());
+        }
+        let mac = bytes.split_off(bytes.len() - MAC_LEN);
+        self.sign(filter, &bytes)
+            .verify_slice(&mac)
+            .map_err(|_| invalid())?;
+        Ok(bytes)
+    }
+
+    fn sign(&self, filter: &RelationTupleFilter, paging_state: &[u8]) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(&self.key).expect("HMAC accepts keys of any length");
+        for field in [
+            &filter.namespace,
+            &filter.object_id,
+            &filter.relation,
+            &filter.user_type,
+            &filter.user_id,
+        ] {
+            // 필드 경계가 모호하지 않도록 존재 여부와 길이를 함께 서명
+            match field {
+                Some(value) => {
+                    mac.update(&[1]);
+                    mac.update(&(value.len() as u64).to_be_bytes());
+                    mac.update(value.as_bytes());
+                }
+                None => mac.update(&[0]),
+            }
+        }
+        mac.update(paging_state);
+        mac
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MemoryTupleStore;
+    use crate::tuple_store::TupleStore;
+
+    fn filter(object_id: &str) -> RelationTupleFilter {
+        RelationTupleFilter {
+            namespace: Some("documents".to_string()),
+            object_id: Some(object_id.to_string()),
+            relation: None,
+            user_type: None,
+            user_id: None,
+        }
+    }
+
+    #[test]
+    fn test_page_token_round_trip() {
+        let codec = PageTokenCodec::new("secret");
+        let token = codec.encode(&filter("doc1"), b"state");
+        assert_eq!(codec.decode(&token, &filter("doc1")).unwrap(), b"state");
+
+        // 다른 필터, 다른 키, 변조된 토큰은 거부
+        assert!(codec.decode(&token, &filter("doc2")).is_err());
+        assert!(PageTokenCodec::new("other").decode(&token, &filter("doc1")).is_err());
+        let mut bytes = URL_SAFE_NO_PAD.decode(&token).unwrap();
+        bytes[0] ^= 1;
+        assert!(codec.decode(&URL_SAFE_NO_PAD.encode(bytes), &filter("doc1")).is_err());
+        assert!(codec.decode("not a token", &filter("doc1")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_read_pages() {
+        let store = MemoryTupleStore::with_tuples(&[
+            "documents:doc1#owner@user:alice",
+            "documents:doc1#viewer@user:bob",
+            "documents:doc1#viewer@user:carol",
+            "documents:doc2#viewer@user:dave",
+        ]);
+        let codec = PageTokenCodec::new("secret");
+
+        // 토큰을 따라가며 모든 튜플을 한 번씩만 읽음
+        let mut users = Vec::new();
+        let mut token: Option<String> = None;
+        loop {
+            let state = token.as_deref().map(|t| codec.decode(t, &filter("doc1"))).transpose().unwrap();
+            let page = store.read_tuples(&filter("doc1"), 2, state).await.unwrap();
+            users.extend(page.tuples.into_iter().map(|t| t.user_id));
+            token = page.paging_state.map(|state| codec.encode(&filter("doc1"), &state));
+            if token.is_none() {
+                break;
+            }
+        }
+        assert_eq!(users, vec!["alice", "bob", "carol"]);
+    }
+}
@@ -1,40 +1,233 @@
 use std::sync::Arc;
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet, VecDeque};
 use async_recursion::async_recursion;
+use chrono::{DateTime, Utc};
 use tracing::{info, warn};
-use crate::models::{RelationTuple, CheckRequest, CheckResponse, BatchCheckRequest, BatchCheckResponse, BatchCheckItem};
-use crate::tuple_store::{TupleStore, ScyllaTupleStore};
-use crate::permission_hierarchy::{PermissionHierarchy, PermissionCheckResult};
+use crate::models::{
+    RelationTuple, WILDCARD_SUBJECT_ID, CheckRequest, CheckResponse, CheckExplanation, ExplainStep, ExplainVia,
+    Permissionship, BatchCheckRequest, BatchCheckResponse, BatchCheckItem, TupleUpdate,
+};
+use crate::caveat::{Caveat, CaveatOutcome, CheckContext};
+use crate::tuple_store::TupleStore;
+use crate::hypothetical::HypotheticalTupleStore;
+use crate::namespace_config::{NamespaceSchema, UsersetRewrite};
+use crate::namespace_store::NamespaceRegistry;
+use crate::permission_hierarchy::PermissionCheckResult;
 use crate::cache::{Cache, CachedCheckResult, CacheKeyBuilder, CacheTTL};
 use crate::zookie::{Zookie, ZookieManager};
 use crate::errors::SentinelResult;
 
+/// 권한을 확인할 주체 (user_type:user_id)
+#[derive(Debug, Clone, Copy)]
+struct Subject<'a> {
+    user_type: &'a str,
+    user_id: &'a str,
+}
+
+/// 재귀 권한 검증 중 공유되는 상태
+struct CheckState {
+    /// 현재 탐색 경로 (순환 참조 방지)
+    visited: HashSet<String>,
+    /// 권한 소스 수집 결과
+    result: PermissionCheckResult,
+    /// 검증 중 읽은 튜플의 가장 이른 만료 시간 (밀리초)
+    earliest_expiry: Option<i64>,
+    /// 조건부 튜플 평가에 사용하는 요청 context
+    context: CheckContext,
+    /// 조건 평가 기준 시간
+    now: DateTime<Utc>,
+    /// 조건부 튜플을 평가했는지 (결과가 context와 시간에 따라 달라짐)
+    contextual: bool,
+    /// 조건 평가에 필요했지만 context에 없던 필드
+    missing_context: BTreeSet<String>,
+    /// exclusion의 subtract를 평가 중인 깊이 (홀수면 일치한 튜플이 권한을 제외함)
+    negated: u32,
+    /// explain 요청 시 권한 해석 경로 추적
+    trace: Option<Trace>,
+}
+
+/// 권한 해석 경로 추적 (explain)
+#[derive(Default)]
+struct Trace {
+    /// 현재 탐색 경로
+    stack: Vec<ExplainStep>,
+    /// 권한을 부여한 경로
+    granted: Option<Vec<ExplainStep>>,
+    /// 권한을 제외한 경로 (exclusion의 subtract)
+    excluded: Option<Vec<ExplainStep>>,
+}
+
+impl Trace {
+    /// 현재 평가 중인 쪽(부여 또는 제외)의 경로
+    fn slot(&mut self, negated: u32) -> &mut Option<Vec<ExplainStep>> {
+        if negated.is_multiple_of(2) { &mut self.granted } else { &mut self.excluded }
+    }
+}
+
+impl CheckState {
+    /// 검증 결과에 영향을 준 튜플 기록 (만료되면 결과가 바뀔 수 있음)
+    /// 튜플에 조건이 있으면 context로 평가해 튜플이 권한을 부여하는지 반환
+    fn admit(&mut self, tuple: &RelationTuple) -> Permissionship {
+        if let Some(expires_at) = tuple.expires_at {
+            self.earliest_expiry = Some(self.earliest_expiry.map_or(expires_at.0, |earliest| earliest.min(expires_at.0)));
+        }
+        let Some(caveat) = &tuple.caveat else {
+            return Permissionship::Allowed;
+        };
+
+        self.contextual = true;
+        let caveat = match Caveat::from_json(caveat) {
+            Ok(caveat) => caveat,
+            Err(e) => {
+                warn!("Ignoring tuple {} with invalid caveat: {}", tuple.to_string_representation(), e);
+                return Permissionship::Denied;
+            }
+        };
+        match caveat.evaluate(&self.context, self.now) {
+            CaveatOutcome::Satisfied => Permissionship::Allowed,
+            CaveatOutcome::Unsatisfied => Permissionship::Denied,
+            CaveatOutcome::MissingContext(fields) => {
+                self.missing_context.extend(fields);
+                Permissionship::Conditional
+            }
+        }
+    }
+
+    /// 탐색 경로에 단계 추가 (explain 요청 시)
+    fn enter(&mut self, namespace: &str, object_id: &str, relation: &str, via: ExplainVia, tuple: Option<&RelationTuple>) {
+        if let Some(trace) = &mut self.trace {
+            trace.stack.push(ExplainStep {
+                userset: format!("{}:{}#{}", namespace, object_id, relation),
+                via,
+                tuple: tuple.map(RelationTuple::to_string_representation),
+            });
+        }
+    }
+
+    fn leave(&mut self) {
+        if let Some(trace) = &mut self.trace {
+            trace.stack.pop();
+        }
+    }
+
+    /// 직접 일치한 튜플까지의 경로 기록 (먼저 찾은 경로 유지)
+    fn record_match(&mut self, namespace: &str, object_id: &str, relation: &str, tuple: &RelationTuple) {
+        let negated = self.negated;
+        let Some(trace) = &mut self.trace else {
+            return;
+        };
+        let mut path = trace.stack.clone();
+        let slot = trace.slot(negated);
+        if slot.is_none() {
+            path.push(ExplainStep {
+                userset: format!("{}:{}#{}", namespace, object_id, relation),
+                via: ExplainVia::Direct,
+                tuple: Some(tuple.to_string_representation()),
+            });
+            *slot = Some(path);
+        }
+    }
+
+    /// 현재 평가 중인 쪽의 경로가 이미 기록되었는지
+    fn recorded(&mut self) -> bool {
+        let negated = self.negated;
+        self.trace.as_mut().is_some_and(|trace| trace.slot(negated).is_some())
+    }
+
+    /// 하위 평가에서 기록한 경로가 최종적으로 허용되지 않았으면 버림
+    /// (예: intersection의 한 쪽만 만족, exclusion으로 제외됨)
+    fn settle(&mut self, recorded_before: bool, permissionship: Permissionship) {
+        let negated = self.negated;
+        if !recorded_before && !permissionship.is_allowed() && let Some(trace) = &mut self.trace {
+            *trace.slot(negated) = None;
+        }
+    }
+}
+
+/// 권한 검증 결과
+struct Evaluation {
+    permissionship: Permissionship,
+    /// conditional일 때 판정에 필요한 context 필드
+    missing_context: Vec<String>,
+    /// 검증 중 읽은 튜플의 가장 이른 만료 시간 (밀리초)
+    earliest_expiry: Option<i64>,
+    /// 조건부 튜플을 평가했는지 (결과를 캐시하지 않음)
+    contextual: bool,
+    /// 권한 해석 경로 (explain 요청 시)
+    explanation: Option<CheckExplanation>,
+}
+
+impl Evaluation {
+    fn into_response(self, zookie: &Zookie) -> SentinelResult<CheckResponse> {
+        Ok(CheckResponse {
+            allowed: self.permissionship.is_allowed(),
+            permissionship: self.permissionship,
+            missing_context: self.missing_context,
+            zookie: zookie.to_string()?,
+            explanation: self.explanation,
+        })
+    }
+}
+
+/// 권한 체크 결과 캐시 TTL (초)
+/// 만료되는 튜플로 계산한 결과는 그 튜플이 만료되기 전에 캐시에서도 만료되도록 제한
+fn check_cache_ttl(earliest_expiry: Option<i64>, now_millis: i64) -> u64 {
+    match earliest_expiry {
+        Some(expires_at) => ((expires_at - now_millis).max(0) / 1000).min(CacheTTL::PERMISSION_CHECK as i64) as u64,
+        None => CacheTTL::PERMISSION_CHECK,
+    }
+}
+
 /// Zanzibar 권한 검증 엔진
-/// 직접 권한, userset 재귀 확인, 권한 상속을 처리
+/// 네임스페이스 설정의 userset rewrite 규칙(this, computed_userset, tuple_to_userset,
+/// union/intersection/exclusion)과 네임스페이스별 권한 계층에 따라
+/// 직접 권한과 userset을 재귀적으로 확인
 pub struct PermissionChecker<C: Cache> {
-    tuple_store: Arc<ScyllaTupleStore>,
-    hierarchy: PermissionHierarchy,
+    tuple_store: Arc<dyn TupleStore>,
+    namespaces: Arc<NamespaceRegistry>,
     cache: Arc<C>,
     zookie_manager: Arc<ZookieManager<C>>,
 }
 
 impl<C: Cache> PermissionChecker<C> {
     /// 새로운 PermissionChecker 생성 (캐시 포함)
-    pub fn new(tuple_store: Arc<ScyllaTupleStore>, cache: Arc<C>, zookie_manager: Arc<ZookieManager<C>>) -> Self {
+    pub fn new(
+        tuple_store: Arc<dyn TupleStore>,
+        namespaces: Arc<NamespaceRegistry>,
+        cache: Arc<C>,
+        zookie_manager: Arc<ZookieManager<C>>,
+    ) -> Self {
         Self {
             tuple_store,
-            hierarchy: PermissionHierarchy::new(),
+            namespaces,
             cache,
             zookie_manager,
         }
     }
 
+    /// 가정한 변경을 저장소에 겹쳐 보는 검증기 (네임스페이스 설정과 캐시는 공유)
+    fn with_hypothetical(&self, updates: &[TupleUpdate]) -> Self {
+        Self {
+            tuple_store: Arc::new(HypotheticalTupleStore::new(self.tuple_store.clone(), updates)),
+            namespaces: self.namespaces.clone(),
+            cache: self.cache.clone(),
+            zookie_manager: self.zookie_manager.clone(),
+        }
+    }
+
     /// 권한 검증 메인 함수 (캐싱 포함)
     pub async fn check_permission(&self, request: &CheckRequest) -> SentinelResult<CheckResponse> {
         // 1. Zookie 검증 및 스냅샷 읽기 시간 결정
         let snapshot_zookie = self.zookie_manager
             .validate_and_get_snapshot_time(request.zookie.as_deref())
             .await?;
+
+        // 가정한 변경이 있으면 저장소에 겹쳐 평가하고, 결과는 캐시에서 읽거나 캐시에 저장하지 않음
+        if !request.hypothetical.is_empty() {
+            return self.with_hypothetical(&request.hypothetical)
+                .evaluate_check(request).await?
+                .into_response(&snapshot_zookie);
+        }
             
         // 2. 캐시에서 먼저 확인
         let cache_key = CacheKeyBuilder::check_permission_key(request);
@@ -44,7 +237,13 @@ impl<C: Cache> PermissionChecker<C> {
                 match CachedCheckResult::from_json(&cached_json) {
                     Ok(cached_result) => {
                         info!("Cache hit for permission check: {}", cache_key);
-                        return Ok(cached_result.to_check_response(&snapshot_zookie.to_string()?));
+                        let mut response = cached_result.to_check_response(&snapshot_zookie.to_string()?);
+                        // explain: 캐시된 결과를 그대로 반환하고, 경로는 저장소 기준으로 다시 평가
+                        if request.explain {
+                            response.explanation = self.evaluate_check(request).await?.explanation
+                                .map(|explanation| CheckExplanation { cached: true, ..explanation });
+                        }
+                        return Ok(response);
                     }
                     Err(e) => {
                         warn!("Failed to deserialize cached result: {}, proceeding without cache", e);
@@ -60,14 +259,22 @@ impl<C: Cache> PermissionChecker<C> {
         }
         
         // 3. 캐시 미스 또는 에러 시 실제 권한 검증 수행
-        let response = self.check_permission_uncached(request, &snapshot_zookie).await?;
+        let evaluation = self.evaluate_check(request).await?;
+        // 1초 안에 만료되는 튜플이나 조건부 튜플로 계산한 결과는 캐시하지 않음
+        let ttl = if evaluation.contextual {
+            0
+        } else {
+            check_cache_ttl(evaluation.earliest_expiry, Utc::now().timestamp_millis())
+        };
+        let response = evaluation.into_response(&snapshot_zookie)?;
         
         // 3. 결과를 캐시에 저장 (비동기, 실패해도 응답에는 영향 없음)
         let cached_result = CachedCheckResult::from_check_response(&response);
-        if let Ok(cached_json) = cached_result.to_json() {
-            if let Err(e) = self.cache.set(&cache_key, &cached_json, CacheTTL::PERMISSION_CHECK).await {
-                warn!("Failed to cache permission result: {}", e);
-            }
+        if ttl > 0
+            && let Ok(cached_json) = cached_result.to_json()
+            && let Err(e) = self.cache.set(&cache_key, &cached_json, ttl).await
+        {
+            warn!("Failed to cache permission result: {}", e);
         }
         
         Ok(response)
@@ -89,7 +296,14 @@ impl<C: Cache> PermissionChecker<C> {
         let mut request_details: Vec<String> = Vec::new();
         
         for (index, check_request) in request.checks.iter().enumerate() {
-            let cache_key = CacheKeyBuilder::check_permission_key(check_request);
+            // context가 다르면 조건부 튜플의 평가 결과가 다르므로 별도 요청으로 취급
+            let mut cache_key = CacheKeyBuilder::check_permission_key(check_request);
+            if !check_request.context.is_empty() {
+                cache_key.push_str(&serde_json::to_string(&check_request.context).unwrap_or_default());
+            }
+            if !check_request.hypothetical.is_empty() {
+                cache_key.push_str(&serde_json::to_string(&check_request.hypothetical).unwrap_or_default());
+            }
             let request_info = format!(
                 "{}:{}#{}@{}", 
                 check_request.namespace,
@@ -99,22 +313,31 @@ impl<C: Cache> PermissionChecker<C> {
             );
             
             request_details.push(request_info);
-            unique_requests.entry(cache_key).or_insert_with(Vec::new).push(index);
+            unique_requests.entry(cache_key).or_default().push(index);
         }
         
         info!("Deduplicated {} requests to {} unique requests", 
               request.checks.len(), unique_requests.len());
         
         // 유니크한 요청들만 병렬로 실행
-        let check_futures = unique_requests.iter().map(|(cache_key, indices)| {
+        let check_futures = unique_requests.values().map(|indices| {
             let checker = self;
             let first_index = indices[0];
             let check_request = &request.checks[first_index];
+            // 배치의 가정한 변경을 각 체크의 가정한 변경 앞에 적용
+            let check_request = if request.hypothetical.is_empty() {
+                check_request.clone()
+            } else {
+                CheckRequest {
+                    hypothetical: [request.hypothetical.as_slice(), check_request.hypothetical.as_slice()].concat(),
+                    ..check_request.clone()
+                }
+            };
             let request_info = request_details[first_index].clone();
             let indices = indices.clone();
             
             async move {
-                let result = checker.check_permission(check_request).await;
+                let result = checker.check_permission(&check_request).await;
                 
                 match result {
                     Ok(response) => {
@@ -122,6 +345,8 @@ impl<C: Cache> PermissionChecker<C> {
                         indices.into_iter().map(|index| BatchCheckItem {
                             request_index: index,
                             allowed: response.allowed,
+                            permissionship: response.permissionship,
+                            missing_context: response.missing_context.clone(),
                             request_info: request_info.clone(),
                         }).collect::<Vec<_>>()
                     },
@@ -130,6 +355,8 @@ impl<C: Cache> PermissionChecker<C> {
                         indices.into_iter().map(|index| BatchCheckItem {
                             request_index: index,
                             allowed: false,
+                            permissionship: Permissionship::Denied,
+                            missing_context: Vec::new(),
                             request_info: format!("{} (ERROR)", request_info),
                         }).collect::<Vec<_>>()
                     }
@@ -159,228 +386,374 @@ impl<C: Cache> PermissionChecker<C> {
     
     /// 캐시를 사용하지 않는 권한 검증 (내부용)
     pub async fn check_permission_uncached(&self, request: &CheckRequest, snapshot_zookie: &Zookie) -> SentinelResult<CheckResponse> {
-        let mut visited = HashSet::new();
-        let mut result = PermissionCheckResult::new(
-            &request.relation,
-            &self.hierarchy,
-        );
+        self.evaluate_check(request).await?.into_response(snapshot_zookie)
+    }
+
+    /// 권한 검증 (조건부 튜플은 요청 context로 평가)
+    async fn evaluate_check(&self, request: &CheckRequest) -> SentinelResult<Evaluation> {
+        let schema = self.namespaces.get(&request.namespace).await?;
+        let mut state = CheckState {
+            visited: HashSet::new(),
+            result: PermissionCheckResult::new(&request.relation, &schema.hierarchy),
+            earliest_expiry: None,
+            context: request.context.clone(),
+            now: Utc::now(),
+            contextual: false,
+            missing_context: BTreeSet::new(),
+            negated: 0,
+            trace: request.explain.then(Trace::default),
+        };
 
-        let user_type = request.user_type.as_deref().unwrap_or("user");
-        let has_permission = self.check_permission_recursive(
+        let subject = Subject {
+            user_type: request.user_type.as_deref().unwrap_or("user"),
+            user_id: &request.user_id,
+        };
+        let permissionship = self.check_permission_recursive(
             &request.namespace,
             &request.object_id,
             &request.relation,
-            user_type,
-            &request.user_id,
-            &mut visited,
-            &mut result,
+            subject,
+            &mut state,
         ).await?;
 
-        Ok(CheckResponse {
-            allowed: has_permission,
-            zookie: snapshot_zookie.to_string()?,
+        let missing_context = match permissionship {
+            Permissionship::Conditional => state.missing_context.into_iter().collect(),
+            _ => Vec::new(),
+        };
+        let explanation = state.trace.map(|trace| CheckExplanation {
+            cached: false,
+            permissionship,
+            path: trace.granted.filter(|_| permissionship.is_allowed()).unwrap_or_default(),
+            excluded_by: trace.excluded.filter(|_| permissionship == Permissionship::Denied).unwrap_or_default(),
+            user_max_permission: state.result.user_max_permission,
+            permission_sources: state.result.permission_sources,
+        });
+        Ok(Evaluation {
+            permissionship,
+            missing_context,
+            earliest_expiry: state.earliest_expiry,
+            contextual: state.contextual,
+            explanation,
         })
     }
 
     /// 재귀적 권한 검증 (순환 참조 방지)
+    /// 네임스페이스 설정의 userset rewrite 규칙을 평가한 뒤, 만족하지 않으면 권한 계층의 상위 관계 확인
     #[async_recursion]
     async fn check_permission_recursive(
         &self,
         namespace: &str,
         object_id: &str,
         relation: &str,
-        user_type: &str,
-        user_id: &str,
-        visited: &mut HashSet<String>,
-        result: &mut PermissionCheckResult,
-    ) -> SentinelResult<bool> {
-        // 순환 참조 방지
-        let check_key = format!("{}:{}#{}@{}:{}", namespace, object_id, relation, user_type, user_id);
-        if visited.contains(&check_key) {
-            return Ok(false);
+        subject: Subject<'async_recursion>,
+        state: &mut CheckState,
+    ) -> SentinelResult<Permissionship> {
+        // 순환 참조 방지 (현재 탐색 경로에 이미 있는 경우)
+        let check_key = format!("{}:{}#{}@{}:{}", namespace, object_id, relation, subject.user_type, subject.user_id);
+        if !state.visited.insert(check_key.clone()) {
+            return Ok(Permissionship::Denied);
         }
-        visited.insert(check_key);
 
-        // 1. 직접 권한 확인
-        if self.check_direct_permission(namespace, object_id, relation, user_type, user_id).await? {
-            result.add_direct_permission(relation, &self.hierarchy);
-            return Ok(true);
-        }
+        let schema = self.namespaces.get(namespace).await?;
+        let allowed = self.check_relation(&schema, object_id, relation, subject, state).await;
+
+        state.visited.remove(&check_key);
+        allowed
+    }
 
-        // 2. 권한 상속 확인 (editor -> viewer 등)
-        if self.check_inherited_permissions(namespace, object_id, relation, user_type, user_id, visited, result).await? {
-            return Ok(true);
+    /// 관계 평가: rewrite 규칙 → 권한 상속 (예: teams의 member -> manager -> owner)
+    /// rewrite 최상위가 exclusion이면 상위 관계로 상속된 권한에도 subtract를 적용
+    /// (예: editor여도 banned이면 viewer 거부)
+    async fn check_relation(
+        &self,
+        schema: &NamespaceSchema,
+        object_id: &str,
+        relation: &str,
+        subject: Subject<'_>,
+        state: &mut CheckState,
+    ) -> SentinelResult<Permissionship> {
+        let (granting, subtract) = match schema.config.rewrite_for(relation) {
+            UsersetRewrite::Exclusion { base, subtract } => (base.as_ref(), Some(subtract.as_ref())),
+            rewrite => (rewrite, None),
+        };
+
+        let recorded = state.recorded();
+
+        // 1. rewrite 규칙 평가 (직접 권한, userset 등)
+        let mut granted = self.evaluate_rewrite(schema, object_id, relation, granting, subject, state).await?;
+
+        // 2. 권한 상속 확인 (바로 위 단계의 관계를 가지면 허용)
+        if !granted.is_allowed() && let Some(higher_permission) = schema.hierarchy.next_higher(relation) {
+            state.enter(schema.name(), object_id, relation, ExplainVia::Hierarchy, None);
+            let inherited = self.check_permission_recursive(
+                schema.name(),
+                object_id,
+                higher_permission,
+                subject,
+                state,
+            ).await?;
+            state.leave();
+            granted = granted.or(inherited);
         }
 
-        // 3. Userset 권한 확인 (팀 멤버십 등)
-        if self.check_userset_permissions(namespace, object_id, relation, user_type, user_id, visited, result).await? {
-            return Ok(true);
+        // 3. 제외 규칙 확인 (거부되지 않은 경우에만 평가)
+        let permissionship = match subtract {
+            Some(subtract) if granted != Permissionship::Denied => {
+                let excluded = self.evaluate_subtract(schema, object_id, relation, subtract, subject, state).await?;
+                granted.and(excluded.negate())
+            }
+            _ => granted,
+        };
+        state.settle(recorded, permissionship);
+        Ok(permissionship)
+    }
+
+    /// exclusion의 subtract 평가 (일치한 경로는 제외 경로로 기록)
+    async fn evaluate_subtract(
+        &self,
+        schema: &NamespaceSchema,
+        object_id: &str,
+        relation: &str,
+        subtract: &UsersetRewrite,
+        subject: Subject<'_>,
+        state: &mut CheckState,
+    ) -> SentinelResult<Permissionship> {
+        state.negated += 1;
+        let recorded = state.recorded();
+        let excluded = self.evaluate_rewrite(schema, object_id, relation, subtract, subject, state).await;
+        if let Ok(excluded) = excluded {
+            state.settle(recorded, excluded);
         }
+        state.negated -= 1;
+        excluded
+    }
+
+    /// userset rewrite 규칙 평가
+    #[async_recursion]
+    async fn evaluate_rewrite(
+        &self,
+        schema: &NamespaceSchema,
+        object_id: &str,
+        relation: &str,
+        rewrite: &UsersetRewrite,
+        subject: Subject<'async_recursion>,
+        state: &mut CheckState,
+    ) -> SentinelResult<Permissionship> {
+        let namespace = schema.name();
+        match rewrite {
+            UsersetRewrite::This => {
+                // 1. 직접 권한 확인
+                let direct = self.check_direct_permission(namespace, object_id, relation, subject, state).await?;
+                if direct.is_allowed() {
+                    if state.negated.is_multiple_of(2) {
+                        state.result.add_direct_permission(relation, &schema.hierarchy);
+                    }
+                    return Ok(direct);
+                }
 
-        Ok(false)
+                // 2. Userset 권한 확인 (팀 멤버십 등)
+                let userset = self.check_userset_permissions(schema, object_id, relation, subject, state).await?;
+                Ok(direct.or(userset))
+            }
+            UsersetRewrite::ComputedUserset { relation: computed } => {
+                state.enter(namespace, object_id, relation, ExplainVia::ComputedUserset, None);
+                let permissionship = self.check_permission_recursive(namespace, object_id, computed, subject, state).await;
+                state.leave();
+                permissionship
+            }
+            UsersetRewrite::TupleToUserset { tupleset, computed_userset } => {
+                self.check_tuple_to_userset(namespace, object_id, relation, tupleset, computed_userset, subject, state).await
+            }
+            UsersetRewrite::Union(children) => {
+                let mut permissionship = Permissionship::Denied;
+                for child in children {
+                    permissionship = permissionship.or(
+                        self.evaluate_rewrite(schema, object_id, relation, child, subject, state).await?,
+                    );
+                    if permissionship.is_allowed() {
+                        break;
+                    }
+                }
+                Ok(permissionship)
+            }
+            UsersetRewrite::Intersection(children) => {
+                if children.is_empty() {
+                    return Ok(Permissionship::Denied);
+                }
+                let recorded = state.recorded();
+                let mut permissionship = Permissionship::Allowed;
+                for child in children {
+                    permissionship = permissionship.and(
+                        self.evaluate_rewrite(schema, object_id, relation, child, subject, state).await?,
+                    );
+                    if permissionship == Permissionship::Denied {
+                        break;
+                    }
+                }
+                state.settle(recorded, permissionship);
+                Ok(permissionship)
+            }
+            // base가 거부되면 subtract는 평가하지 않음
+            UsersetRewrite::Exclusion { base, subtract } => {
+                let recorded = state.recorded();
+                let base = self.evaluate_rewrite(schema, object_id, relation, base, subject, state).await?;
+                if base == Permissionship::Denied {
+                    return Ok(base);
+                }
+                let excluded = self.evaluate_subtract(schema, object_id, relation, subtract, subject, state).await?;
+                let permissionship = base.and(excluded.negate());
+                state.settle(recorded, permissionship);
+                Ok(permissionship)
+            }
+        }
     }
 
-    /// 직접 권한 확인 (정확히 일치하는 튜플)
+    /// 직접 권한 확인 (정확히 일치하는 튜플 또는 같은 타입의 와일드카드 튜플)
+    /// 튜플에 조건이 있으면 요청 context로 평가
     async fn check_direct_permission(
         &self,
         namespace: &str,
         object_id: &str,
         relation: &str,
-        user_type: &str,
-        user_id: &str,
-    ) -> SentinelResult<bool> {
+        subject: Subject<'_>,
+        state: &mut CheckState,
+    ) -> SentinelResult<Permissionship> {
         let tuple = RelationTuple {
             namespace: namespace.to_string(),
             object_id: object_id.to_string(),
             relation: relation.to_string(),
-            user_type: user_type.to_string(),
-            user_id: user_id.to_string(),
+            user_type: subject.user_type.to_string(),
+            user_id: subject.user_id.to_string(),
             created_at: scylla::value::CqlTimestamp(0),
+            expires_at: None,
+            caveat: None,
         };
 
-        let found = self.tuple_store.find_direct_tuple(&tuple).await?;
-        Ok(found.is_some())
-    }
-
-    /// 권한 상속 확인 (owner -> admin -> editor -> viewer)
-    #[async_recursion]
-    async fn check_inherited_permissions(
-        &self,
-        namespace: &str,
-        object_id: &str,
-        relation: &str,
-        user_type: &str,
-        user_id: &str,
-        visited: &mut HashSet<String>,
-        result: &mut PermissionCheckResult,
-    ) -> SentinelResult<bool> {
-        let inherited_permissions = self.hierarchy.get_inherited_permissions(relation);
-        
-        for higher_permission in inherited_permissions {
-            if self.check_permission_recursive(
-                namespace,
-                object_id,
-                &higher_permission,
-                user_type,
-                user_id,
-                visited,
-                result,
-            ).await? {
-                return Ok(true);
+        let mut permissionship = Permissionship::Denied;
+        if let Some(found) = self.tuple_store.find_direct_tuple(&tuple).await? {
+            permissionship = state.admit(&found);
+            if permissionship.is_allowed() {
+                state.record_match(namespace, object_id, relation, &found);
             }
         }
 
-        Ok(false)
+        // 와일드카드 주체 (예: documents:doc1#viewer@user:*)는 같은 타입의 모든 주체에게 허용
+        if !permissionship.is_allowed() && subject.user_type != "userset" && subject.user_id != WILDCARD_SUBJECT_ID {
+            let wildcard = RelationTuple {
+                user_id: WILDCARD_SUBJECT_ID.to_string(),
+                ..tuple
+            };
+            if let Some(found) = self.tuple_store.find_direct_tuple(&wildcard).await? {
+                let granted = state.admit(&found);
+                if granted.is_allowed() {
+                    state.record_match(namespace, object_id, relation, &found);
+                }
+                permissionship = permissionship.or(granted);
+            }
+        }
+        Ok(permissionship)
     }
 
     /// Userset 권한 확인 (팀 멤버십 기반 간접 권한)
     async fn check_userset_permissions(
         &self,
-        namespace: &str,
+        schema: &NamespaceSchema,
         object_id: &str,
         relation: &str,
-        user_type: &str,
-        user_id: &str,
-        visited: &mut HashSet<String>,
-        result: &mut PermissionCheckResult,
-    ) -> SentinelResult<bool> {
+        subject: Subject<'_>,
+        state: &mut CheckState,
+    ) -> SentinelResult<Permissionship> {
         // 해당 객체-관계에 대한 모든 권한 튜플 조회
         let all_tuples = self.tuple_store.find_tuples_by_object_relation(
-            namespace,
+            schema.name(),
             object_id,
             relation,
         ).await?;
 
+        let mut permissionship = Permissionship::Denied;
         for tuple in all_tuples {
-            // userset 형태인지 확인 (user_type이 'userset')
-            if tuple.user_type == "userset" {
-                // userset_id 파싱: "teams:backend#member" -> (teams, backend, member)
-                if let Some((userset_namespace, userset_object_relation)) = tuple.user_id.split_once(':') {
-                    if let Some((userset_object, userset_relation)) = userset_object_relation.split_once('#') {
-                        // 사용자가 해당 userset에 속하는지 확인
-                        if self.check_permission_recursive(
-                            userset_namespace,
-                            userset_object,
-                            userset_relation,
-                            user_type,
-                            user_id,
-                            visited,
-                            result,
-                        ).await? {
-                            result.add_team_permission(userset_namespace, &tuple.user_id, &self.hierarchy);
-                            return Ok(true);
-                        }
-                    }
+            // userset 형태인지 확인: "teams:backend#member" -> (teams, backend, member)
+            let Some((userset_namespace, userset_object, userset_relation)) = tuple.userset_reference() else {
+                continue;
+            };
+            let granted = state.admit(&tuple);
+            if granted == Permissionship::Denied {
+                continue;
+            }
+
+            // 사용자가 해당 userset에 속하는지 확인
+            let recorded = state.recorded();
+            state.enter(schema.name(), object_id, relation, ExplainVia::Userset, Some(&tuple));
+            let member = self.check_permission_recursive(
+                userset_namespace,
+                userset_object,
+                userset_relation,
+                subject,
+                state,
+            ).await?;
+            state.leave();
+            let result = granted.and(member);
+            state.settle(recorded, result);
+            permissionship = permissionship.or(result);
+            if permissionship.is_allowed() {
+                if state.negated.is_multiple_of(2) {
+                    state.result.add_team_permission(relation, &tuple.user_id, &schema.hierarchy);
                 }
+                break;
             }
         }
 
-        Ok(false)
+        Ok(permissionship)
     }
 
-    /// Userset 멤버십 확인 (예: user:alice가 team:backend#member에 속하는가?)
-    #[async_recursion]
-    async fn check_userset_membership(
+    /// Tuple-to-userset 확인 (tupleset 관계로 연결된 객체의 관계를 따라감)
+    /// 예: document:doc1#parent@folder:x 가 있으면 document:doc1#viewer -> folder:x#viewer
+    #[allow(clippy::too_many_arguments)]
+    async fn check_tuple_to_userset(
         &self,
-        userset_type: &str,
-        userset_id: &str,
-        user_type: &str,
-        user_id: &str,
-        visited: &mut HashSet<String>,
-    ) -> SentinelResult<bool> {
-        // userset이 팀인 경우 멤버십 확인
-        if userset_type == "team" {
-            return self.check_team_membership(userset_id, user_id).await;
-        }
+        namespace: &str,
+        object_id: &str,
+        relation: &str,
+        tupleset: &str,
+        computed_userset: &str,
+        subject: Subject<'_>,
+        state: &mut CheckState,
+    ) -> SentinelResult<Permissionship> {
+        let tupleset_tuples = self.tuple_store.find_tuples_by_object_relation(
+            namespace,
+            object_id,
+            tupleset,
+        ).await?;
 
-        // 다른 userset 타입들에 대한 재귀적 확인
-        // 예: group:editors#member -> team:backend#member
-        let userset_tuples = self.tuple_store.find_tuples_by_object(userset_type, userset_id).await?;
-        
-        for tuple in userset_tuples {
-            if tuple.user_type == user_type && tuple.user_id == user_id {
-                return Ok(true);
+        let mut permissionship = Permissionship::Denied;
+        for tuple in tupleset_tuples {
+            let Some((parent_namespace, parent_object)) = tuple.subject_object() else {
+                continue;
+            };
+            let linked = state.admit(&tuple);
+            if linked == Permissionship::Denied {
+                continue;
             }
-            
-            // 중첩된 userset 확인 (재귀)
-            if tuple.user_type != "user" {
-                let membership_key = format!("membership:{}:{}@{}:{}", 
-                    userset_type, userset_id, user_type, user_id);
-                if !visited.contains(&membership_key) {
-                    visited.insert(membership_key);
-                    
-                    if self.check_userset_membership(
-                        &tuple.user_type,
-                        &tuple.user_id,
-                        user_type,
-                        user_id,
-                        visited,
-                    ).await? {
-                        return Ok(true);
-                    }
-                }
+
+            let recorded = state.recorded();
+            state.enter(namespace, object_id, relation, ExplainVia::TupleToUserset, Some(&tuple));
+            let inherited = self.check_permission_recursive(
+                parent_namespace,
+                parent_object,
+                computed_userset,
+                subject,
+                state,
+            ).await?;
+            state.leave();
+            let result = linked.and(inherited);
+            state.settle(recorded, result);
+            permissionship = permissionship.or(result);
+            if permissionship.is_allowed() {
+                break;
             }
         }
 
-        Ok(false)
-    }
-
-    /// 팀 멤버십 확인 (Team Service와 연동)
-    async fn check_team_membership(&self, team_id: &str, user_id: &str) -> SentinelResult<bool> {
-        // TODO: Team Service API 호출로 실제 팀 멤버십 확인
-        // 지금은 데이터베이스에서 직접 확인
-        
-        let membership_tuple = RelationTuple {
-            namespace: "team".to_string(),
-            object_id: team_id.to_string(),
-            relation: "member".to_string(),
-            user_type: "user".to_string(),
-            user_id: user_id.to_string(),
-            created_at: scylla::value::CqlTimestamp(0),
-        };
-
-        let found = self.tuple_store.find_direct_tuple(&membership_tuple).await?;
-        Ok(found.is_some())
+        Ok(permissionship)
     }
 
     /// 사용자의 모든 권한 조회 (디버깅 및 권한 확인용)
@@ -393,6 +766,36 @@ impl<C: Cache> PermissionChecker<C> {
         self.tuple_store.find_tuples_by_object(namespace, object_id).await
     }
     
+    /// 객체의 관계를 상속받는 객체들 (간접 상속 포함, 자기 자신 제외)
+    /// 객체 자체(tuple_to_userset의 부모)나 객체의 userset이 주체로 저장된 튜플을 거슬러 올라감
+    /// 예: folders:root -> folders:sub (parent) -> documents:doc1 (parent)
+    pub async fn dependent_objects(&self, namespace: &str, object_id: &str) -> SentinelResult<Vec<(String, String)>> {
+        let mut visited: HashSet<(String, String)> = HashSet::new();
+        let mut queue: VecDeque<(String, String)> = VecDeque::new();
+        let mut dependents = Vec::new();
+        visited.insert((namespace.to_string(), object_id.to_string()));
+        queue.push_back((namespace.to_string(), object_id.to_string()));
+
+        while let Some((object_namespace, object_id)) = queue.pop_front() {
+            let mut referencing = self.tuple_store.find_subject_memberships(&object_namespace, &object_id).await?;
+            let schema = self.namespaces.get(&object_namespace).await?;
+            for relation in schema.config.relation_names() {
+                let userset = format!("{}:{}#{}", object_namespace, object_id, relation);
+                referencing.extend(self.tuple_store.find_subject_memberships("userset", &userset).await?);
+            }
+
+            for tuple in referencing {
+                let object = (tuple.namespace, tuple.object_id);
+                if visited.insert(object.clone()) {
+                    dependents.push(object.clone());
+                    queue.push_back(object);
+                }
+            }
+        }
+
+        Ok(dependents)
+    }
+
     /// 사용자와 관련된 모든 권한 캐시 무효화
     pub async fn invalidate_user_cache(&self, user_id: &str) -> SentinelResult<()> {
         let pattern = CacheKeyBuilder::user_permission_pattern(user_id);
@@ -424,9 +827,9 @@ impl<C: Cache> PermissionChecker<C> {
     }
     
     /// 네임스페이스와 관련된 모든 권한 캐시 무효화
+    /// 다른 네임스페이스의 체크도 userset이나 tuple_to_userset으로 이 네임스페이스를 거칠 수 있으므로 전체 무효화
     pub async fn invalidate_namespace_cache(&self, namespace: &str) -> SentinelResult<()> {
-        let pattern = CacheKeyBuilder::namespace_permission_pattern(namespace);
-        match self.cache.delete_pattern(&pattern).await {
+        match self.cache.delete_pattern(CacheKeyBuilder::all_permissions_pattern()).await {
             Ok(_) => {
                 info!("Invalidated cache for namespace: {}", namespace);
                 Ok(())
@@ -439,6 +842,7 @@ impl<C: Cache> PermissionChecker<C> {
     }
     
     /// 특정 권한 체크 캐시만 무효화
+    #[allow(dead_code)]
     pub async fn invalidate_specific_cache(&self, request: &CheckRequest) -> SentinelResult<()> {
         let cache_key = CacheKeyBuilder::check_permission_key(request);
         match self.cache.delete(&cache_key).await {
@@ -452,4 +856,394 @@ impl<C: Cache> PermissionChecker<C> {
             }
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scylla::value::CqlTimestamp;
+    use crate::models::Operation;
+    use crate::test_utils::{parse_tuple, MemoryTupleStore, MockCache, MemoryNamespaceStore};
+
+    const DOCUMENTS: &str = r#"{
+        "name": "documents",
+        "relations": [
+            {"name": "owner"},
+            {"name": "parent"},
+            {"name": "banned"},
+            {"name": "editor", "rewrite": {"union": ["this", {"computed_userset": {"relation": "owner"}}]}},
+            {"name": "viewer", "rewrite": {"exclusion": {
+                "base": {"union": [
+                    "this",
+                    {"computed_userset": {"relation": "editor"}},
+                    {"tuple_to_userset": {"tupleset": "parent", "computed_userset": "viewer"}}
+                ]},
+                "subtract": {"computed_userset": {"relation": "banned"}}
+            }}}
+        ]
+    }"#;
+
+    const FOLDERS: &str = r#"{
+        "name": "folders",
+        "relations": [
+            {"name": "parent"},
+            {"name": "viewer", "rewrite": {"union": [
+                "this",
+                {"tuple_to_userset": {"tupleset": "parent", "computed_userset": "viewer"}}
+            ]}}
+        ]
+    }"#;
+
+    const TEAMS: &str = r#"{"name": "teams", "hierarchy": ["owner", "manager", "member"]}"#;
+
+    const REPORTS: &str = r#"{
+        "name": "reports",
+        "hierarchy": ["owner", "editor", "viewer"],
+        "relations": [
+            {"name": "owner"},
+            {"name": "editor"},
+            {"name": "org"},
+            {"name": "banned"},
+            {"name": "viewer", "rewrite": {"exclusion": {
+                "base": "this",
+                "subtract": {"computed_userset": {"relation": "banned"}}
+            }}},
+            {"name": "publisher", "rewrite": {"intersection": [
+                {"computed_userset": {"relation": "editor"}},
+                {"tuple_to_userset": {"tupleset": "org", "computed_userset": "member"}}
+            ]}}
+        ]
+    }"#;
+
+    fn checker(tuples: &[&str]) -> PermissionChecker<MockCache> {
+        let cache = Arc::new(MockCache::new());
+        let namespaces = MemoryNamespaceStore::from_json(&[DOCUMENTS, FOLDERS, TEAMS, REPORTS]);
+        PermissionChecker::new(
+            Arc::new(MemoryTupleStore::with_tuples(tuples)),
+            Arc::new(NamespaceRegistry::new(Arc::new(namespaces))),
+            cache.clone(),
+            Arc::new(ZookieManager::new(cache, None)),
+        )
+    }
+
+    async fn check(checker: &PermissionChecker<MockCache>, namespace: &str, object_id: &str, relation: &str, user_id: &str) -> bool {
+        let request = CheckRequest {
+            namespace: namespace.to_string(),
+            object_id: object_id.to_string(),
+            relation: relation.to_string(),
+            user_id: user_id.to_string(),
+            user_type: None,
+            zookie: None,
+            context: Default::default(),
+            explain: false,
+            hypothetical: Vec::new(),
+        };
+        checker.check_permission_uncached(&request, &Zookie::new()).await.unwrap().allowed
+    }
+
+    #[tokio::test]
+    async fn test_rewrite_rules() {
+        let checker = checker(&[
+            "documents:doc1#owner@user:alice",
+            "documents:doc1#editor@userset:teams:backend#member",
+            "documents:doc1#parent@folders:shared",
+            "documents:doc1#banned@user:mallory",
+            "teams:backend#member@user:bob",
+            "folders:shared#viewer@user:carol",
+            "folders:shared#viewer@user:mallory",
+        ]);
+
+        // computed_userset: owner -> editor -> viewer
+        assert!(check(&checker, "documents", "doc1", "viewer", "alice").await);
+        // this (userset): teams:backend#member -> editor
+        assert!(check(&checker, "documents", "doc1", "editor", "bob").await);
+        assert!(check(&checker, "documents", "doc1", "viewer", "bob").await);
+        // tuple_to_userset: parent -> folders:shared#viewer
+        assert!(check(&checker, "documents", "doc1", "viewer", "carol").await);
+        assert!(!check(&checker, "documents", "doc1", "editor", "carol").await);
+        // exclusion: banned
+        assert!(!check(&checker, "documents", "doc1", "viewer", "mallory").await);
+        assert!(!check(&checker, "documents", "doc1", "viewer", "dave").await);
+    }
+
+    #[tokio::test]
+    async fn test_intersection_and_exclusion() {
+        let checker = checker(&[
+            "reports:p1#owner@user:alice",
+            "reports:p1#editor@userset:teams:backend#member",
+            "reports:p1#editor@user:carol",
+            "reports:p1#org@teams:acme",
+            "reports:p1#banned@user:bob",
+            "teams:backend#member@user:bob",
+            "teams:backend#member@user:dave",
+            "teams:acme#member@user:carol",
+            "teams:acme#manager@user:dave",
+        ]);
+
+        // exclusion은 팀과 상위 관계로 상속된 권한보다 우선
+        assert!(check(&checker, "reports", "p1", "viewer", "alice").await);
+        assert!(check(&checker, "reports", "p1", "viewer", "dave").await);
+        assert!(check(&checker, "reports", "p1", "editor", "bob").await);
+        assert!(!check(&checker, "reports", "p1", "viewer", "bob").await);
+        // intersection: editor이면서 org 팀의 member (상위 팀 역할 포함)
+        assert!(check(&checker, "reports", "p1", "publisher", "carol").await);
+        assert!(check(&checker, "reports", "p1", "publisher", "dave").await);
+        assert!(!check(&checker, "reports", "p1", "publisher", "alice").await);
+        assert!(!check(&checker, "reports", "p1", "publisher", "bob").await);
+    }
+
+    #[tokio::test]
+    async fn test_nested_parent_objects() {
+        let checker = checker(&[
+            "documents:doc1#parent@folders:sub",
+            "documents:doc2#viewer@userset:folders:sub#viewer",
+            "folders:sub#parent@folders:root",
+            "folders:root#parent@folders:sub",
+            "folders:root#viewer@userset:teams:backend#member",
+            "teams:backend#member@user:bob",
+        ]);
+
+        // documents:doc1 -> folders:sub -> folders:root -> teams:backend#member (순환하는 parent는 무시)
+        assert!(check(&checker, "documents", "doc1", "viewer", "bob").await);
+        assert!(!check(&checker, "documents", "doc1", "viewer", "carol").await);
+
+        // folders:root의 권한이 바뀌면 캐시를 무효화해야 하는 객체
+        let mut dependents = checker.dependent_objects("folders", "root").await.unwrap();
+        dependents.sort();
+        assert_eq!(dependents, vec![
+            ("documents".to_string(), "doc1".to_string()),
+            ("documents".to_string(), "doc2".to_string()),
+            ("folders".to_string(), "sub".to_string()),
+        ]);
+    }
+
+    #[tokio::test]
+    async fn test_namespace_hierarchy() {
+        let checker = checker(&[
+            "teams:backend#owner@user:alice",
+            "teams:backend#manager@user:bob",
+            "documents:doc1#viewer@userset:teams:backend#member",
+        ]);
+
+        assert!(check(&checker, "teams", "backend", "member", "alice").await);
+        assert!(check(&checker, "teams", "backend", "manager", "alice").await);
+        assert!(check(&checker, "teams", "backend", "member", "bob").await);
+        assert!(!check(&checker, "teams", "backend", "owner", "bob").await);
+        // 상위 팀 역할도 팀 멤버 userset에 포함
+        assert!(check(&checker, "documents", "doc1", "viewer", "bob").await);
+    }
+
+    #[tokio::test]
+    async fn test_wildcard_subject() {
+        let checker = checker(&[
+            "documents:doc1#viewer@user:*",
+            "documents:doc1#banned@user:mallory",
+            "documents:doc2#parent@folders:public",
+            "folders:public#viewer@user:*",
+        ]);
+
+        assert!(check(&checker, "documents", "doc1", "viewer", "alice").await);
+        assert!(!check(&checker, "documents", "doc1", "editor", "alice").await);
+        // 와일드카드로 허용되어도 exclusion은 적용
+        assert!(!check(&checker, "documents", "doc1", "viewer", "mallory").await);
+        // tuple_to_userset으로 연결된 공개 폴더
+        assert!(check(&checker, "documents", "doc2", "viewer", "bob").await);
+    }
+
+    #[tokio::test]
+    async fn test_expiring_tuples() {
+        let now = Utc::now().timestamp_millis();
+        let store = MemoryTupleStore::with_tuples(&["teams:backend#member@user:bob"]);
+        store.write_tuples(&[
+            (Operation::Insert, parse_tuple("documents:doc1#viewer@user:alice").with_expiry(Some(CqlTimestamp(now - 1000)))),
+            (Operation::Insert, parse_tuple("documents:doc1#viewer@userset:teams:backend#member").with_expiry(Some(CqlTimestamp(now + 60_000)))),
+        ]).await.unwrap();
+        let cache = Arc::new(MockCache::new());
+        let namespaces = MemoryNamespaceStore::from_json(&[DOCUMENTS, FOLDERS, TEAMS]);
+        let checker = PermissionChecker::new(
+            Arc::new(store),
+            Arc::new(NamespaceRegistry::new(Arc::new(namespaces))),
+            cache.clone(),
+            Arc::new(ZookieManager::new(cache, None)),
+        );
+
+        // 만료된 직접 권한은 무시
+        assert!(!check(&checker, "documents", "doc1", "viewer", "alice").await);
+
+        // userset을 통한 권한은 만료 전까지 허용되며, 결과는 만료 시간보다 오래 캐시되지 않음
+        let request = CheckRequest {
+            namespace: "documents".to_string(),
+            object_id: "doc1".to_string(),
+            relation: "viewer".to_string(),
+            user_id: "bob".to_string(),
+            user_type: None,
+            zookie: None,
+            context: Default::default(),
+            explain: false,
+            hypothetical: Vec::new(),
+        };
+        let evaluation = checker.evaluate_check(&request).await.unwrap();
+        assert!(evaluation.permissionship.is_allowed());
+        assert_eq!(evaluation.earliest_expiry, Some(now + 60_000));
+        assert_eq!(check_cache_ttl(evaluation.earliest_expiry, now), 60);
+        assert_eq!(check_cache_ttl(Some(now + 500), now), 0);
+        assert_eq!(check_cache_ttl(None, now), CacheTTL::PERMISSION_CHECK);
+    }
+
+    #[tokio::test]
+    async fn test_caveated_tuples() {
+        let checker = checker(&["documents:doc1#viewer@user:bob"]);
+        let office: Caveat = serde_json::from_str(r#"{"ip_in_cidr": {"field": "ip", "cidrs": ["10.0.0.0/8"]}}"#).unwrap();
+        let suspended: Caveat = serde_json::from_str(r#"{"attribute": {"field": "suspended", "op": "eq", "value": true}}"#).unwrap();
+        checker.tuple_store.write_tuples(&[
+            (Operation::Insert, parse_tuple("documents:doc1#viewer@user:alice").with_caveat(Some(&office))),
+            (Operation::Insert, parse_tuple("documents:doc1#banned@user:bob").with_caveat(Some(&suspended))),
+        ]).await.unwrap();
+
+        let check_with = |user_id: &str, context: serde_json::Value| CheckRequest {
+            namespace: "documents".to_string(),
+            object_id: "doc1".to_string(),
+            relation: "viewer".to_string(),
+            user_id: user_id.to_string(),
+            user_type: None,
+            zookie: None,
+            context: serde_json::from_value(context).unwrap(),
+            explain: false,
+            hypothetical: Vec::new(),
+        };
+
+        // context가 없으면 조건부, 필요한 필드 반환
+        let response = checker.check_permission(&check_with("alice", serde_json::json!({}))).await.unwrap();
+        assert_eq!(response.permissionship, Permissionship::Conditional);
+        assert!(!response.allowed);
+        assert_eq!(response.missing_context, vec!["ip"]);
+
+        // 조건부 튜플로 계산한 결과는 캐시하지 않으므로 context마다 다시 평가
+        let response = checker.check_permission(&check_with("alice", serde_json::json!({"ip": "10.1.2.3"}))).await.unwrap();
+        assert_eq!(response.permissionship, Permissionship::Allowed);
+        let response = checker.check_permission(&check_with("alice", serde_json::json!({"ip": "192.168.0.1"}))).await.unwrap();
+        assert_eq!(response.permissionship, Permissionship::Denied);
+        assert!(response.missing_context.is_empty());
+
+        // exclusion의 조건을 평가할 수 없으면 허용하지 않고 조건부
+        let response = checker.check_permission(&check_with("bob", serde_json::json!({}))).await.unwrap();
+        assert_eq!(response.permissionship, Permissionship::Conditional);
+        assert_eq!(response.missing_context, vec!["suspended"]);
+        let response = checker.check_permission(&check_with("bob", serde_json::json!({"suspended": false}))).await.unwrap();
+        assert_eq!(response.permissionship, Permissionship::Allowed);
+        let response = checker.check_permission(&check_with("bob", serde_json::json!({"suspended": true}))).await.unwrap();
+        assert_eq!(response.permissionship, Permissionship::Denied);
+    }
+
+    #[tokio::test]
+    async fn test_explain() {
+        let checker = checker(&[
+            "documents:doc1#editor@userset:teams:backend#member",
+            "documents:doc1#banned@user:mallory",
+            "teams:backend#manager@user:bob",
+            "teams:backend#member@user:mallory",
+        ]);
+        let explain = |user_id: &str| CheckRequest {
+            namespace: "documents".to_string(),
+            object_id: "doc1".to_string(),
+            relation: "viewer".to_string(),
+            user_id: user_id.to_string(),
+            user_type: None,
+            zookie: None,
+            context: Default::default(),
+            explain: true,
+            hypothetical: Vec::new(),
+        };
+        let step = |userset: &str, via: ExplainVia, tuple: Option<&str>| ExplainStep {
+            userset: userset.to_string(),
+            via,
+            tuple: tuple.map(String::from),
+        };
+
+        // viewer -> editor -> 팀 userset -> 팀 계층(member -> manager) -> 직접 튜플
+        let explanation = checker.check_permission(&explain("bob")).await.unwrap().explanation.unwrap();
+        assert!(!explanation.cached);
+        assert_eq!(explanation.path, vec![
+            step("documents:doc1#viewer", ExplainVia::ComputedUserset, None),
+            step("documents:doc1#editor", ExplainVia::Userset, Some("documents:doc1#editor@userset:teams:backend#member")),
+            step("teams:backend#member", ExplainVia::Hierarchy, None),
+            step("teams:backend#manager", ExplainVia::Direct, Some("teams:backend#manager@user:bob")),
+        ]);
+        assert!(explanation.excluded_by.is_empty());
+
+        // 캐시된 결과도 경로는 다시 평가
+        let explanation = checker.check_permission(&explain("bob")).await.unwrap().explanation.unwrap();
+        assert!(explanation.cached);
+        assert_eq!(explanation.path.len(), 4);
+
+        // exclusion으로 거부되면 부여 경로 대신 제외 경로
+        let explanation = checker.check_permission(&explain("mallory")).await.unwrap().explanation.unwrap();
+        assert_eq!(explanation.permissionship, Permissionship::Denied);
+        assert!(explanation.path.is_empty());
+        assert_eq!(explanation.excluded_by, vec![
+            step("documents:doc1#viewer", ExplainVia::ComputedUserset, None),
+            step("documents:doc1#banned", ExplainVia::Direct, Some("documents:doc1#banned@user:mallory")),
+        ]);
+
+        // explain을 요청하지 않으면 포함하지 않음
+        assert!(check(&checker, "documents", "doc1", "viewer", "bob").await);
+        let request = CheckRequest { explain: false, ..explain("bob") };
+        assert!(checker.check_permission(&request).await.unwrap().explanation.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_hypothetical_tuples() {
+        let checker = checker(&[
+            "documents:doc1#editor@userset:teams:backend#member",
+            "teams:backend#member@user:alice",
+        ]);
+        let update = |operation: Operation, tuple: &str| TupleUpdate {
+            operation,
+            tuple: parse_tuple(tuple).to_api_tuple(),
+        };
+        let what_if = |user_id: &str, hypothetical: Vec<TupleUpdate>| CheckRequest {
+            namespace: "documents".to_string(),
+            object_id: "doc1".to_string(),
+            relation: "viewer".to_string(),
+            user_id: user_id.to_string(),
+            user_type: None,
+            zookie: None,
+            context: Default::default(),
+            explain: false,
+            hypothetical,
+        };
+        let remove_alice = update(Operation::Delete, "teams:backend#member@user:alice");
+        let share_with_carol = update(Operation::Touch, "documents:doc1#viewer@user:carol");
+
+        // 캐시된 결과가 있어도 가정한 변경으로 다시 평가
+        assert!(checker.check_permission(&what_if("alice", Vec::new())).await.unwrap().allowed);
+        assert!(!checker.check_permission(&what_if("alice", vec![remove_alice.clone()])).await.unwrap().allowed);
+        assert!(checker.check_permission(&what_if("carol", vec![share_with_carol.clone()])).await.unwrap().allowed);
+
+        // 가정한 변경은 저장하거나 캐시하지 않음
+        assert!(checker.check_permission(&what_if("alice", Vec::new())).await.unwrap().allowed);
+        assert!(!checker.check_permission(&what_if("carol", Vec::new())).await.unwrap().allowed);
+        assert!(checker.tuple_store.find_direct_tuple(&parse_tuple("teams:backend#member@user:alice")).await.unwrap().is_some());
+
+        // 배치의 가정한 변경은 모든 체크에, 체크의 가정한 변경은 그 체크에만 적용
+        let batch = BatchCheckRequest {
+            checks: vec![
+                what_if("alice", Vec::new()),
+                what_if("carol", Vec::new()),
+                what_if("carol", vec![share_with_carol]),
+            ],
+            zookie: None,
+            hypothetical: vec![remove_alice],
+        };
+        let results = checker.batch_check_permissions(&batch).await.unwrap().results;
+        assert_eq!(results.iter().map(|item| item.allowed).collect::<Vec<_>>(), vec![false, false, true]);
+    }
+
+    #[tokio::test]
+    async fn test_legacy_hierarchy_for_unregistered_namespace() {
+        let checker = checker(&["projects:webapp#admin@user:alice"]);
+
+        assert!(check(&checker, "projects", "webapp", "viewer", "alice").await);
+        assert!(check(&checker, "projects", "webapp", "editor", "alice").await);
+        assert!(!check(&checker, "projects", "webapp", "owner", "alice").await);
+    }
+}
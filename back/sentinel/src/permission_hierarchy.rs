@@ -40,6 +40,29 @@ impl PermissionHierarchy {
         Self { levels, inheritance }
     }
     
+    /// 순서가 있는 관계 목록으로 권한 계층 생성 (높은 권한부터)
+    /// 예: ["owner", "manager", "member"] -> owner(3) > manager(2) > member(1)
+    pub fn from_ordered(relations: &[String]) -> Self {
+        let mut levels = HashMap::new();
+        let mut inheritance = HashMap::new();
+
+        for (index, relation) in relations.iter().enumerate() {
+            levels.insert(relation.clone(), (relations.len() - index) as u8);
+            inheritance.insert(relation.clone(), relations[index + 1..].to_vec());
+        }
+
+        Self { levels, inheritance }
+    }
+
+    /// 바로 위 단계의 권한 반환 (예: member -> manager)
+    pub fn next_higher(&self, permission: &str) -> Option<&str> {
+        let current_level = self.levels.get(permission).copied()?;
+        self.levels
+            .iter()
+            .find(|&(_, &level)| level == current_level + 1)
+            .map(|(perm, _)| perm.as_str())
+    }
+    
     /// 권한 레벨 조회
     pub fn get_level(&self, permission: &str) -> u8 {
         self.levels.get(permission).copied().unwrap_or(0)
@@ -94,7 +117,7 @@ impl PermissionHierarchy {
     }
     
     /// 권한 목록을 레벨 순으로 정렬 (높은 권한부터)
-    pub fn sort_by_level(&self, permissions: &mut Vec<String>) {
+    pub fn sort_by_level(&self, permissions: &mut [String]) {
         permissions.sort_by(|a, b| {
             let level_a = self.get_level(a);
             let level_b = self.get_level(b);
@@ -232,6 +255,25 @@ mod tests {
         assert!(!hierarchy.includes("viewer", "editor"));
     }
     
+    #[test]
+    fn test_ordered_hierarchy() {
+        let relations = ["owner", "manager", "member"].map(String::from);
+        let hierarchy = PermissionHierarchy::from_ordered(&relations);
+
+        assert_eq!(hierarchy.get_level("owner"), 3);
+        assert_eq!(hierarchy.get_level("member"), 1);
+        assert_eq!(hierarchy.get_level("viewer"), 0);
+
+        assert_eq!(hierarchy.next_higher("member"), Some("manager"));
+        assert_eq!(hierarchy.next_higher("manager"), Some("owner"));
+        assert_eq!(hierarchy.next_higher("owner"), None);
+        assert_eq!(hierarchy.next_higher("viewer"), None);
+
+        assert!(hierarchy.includes("owner", "member"));
+        assert!(!hierarchy.includes("member", "manager"));
+        assert_eq!(hierarchy.get_all_permissions(), relations.to_vec());
+    }
+    
     #[test]
     fn test_permission_check_result() {
         let hierarchy = PermissionHierarchy::new();
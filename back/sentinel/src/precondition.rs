@@ -0,0 +1,114 @@
+use tracing::info;
+use crate::models::{Precondition, PreconditionOperation};
+use crate::query_plan::QueryPlan;
+use crate::tuple_store::TupleStore;
+use crate::errors::{SentinelError, SentinelResult, Violation};
+
+/// 쓰기 요청의 선행 조건 평가
+/// 조회할 수 없는 필터가 있으면 아무것도 조회하지 않고 검증 에러,
+/// 만족하지 않는 조건이 있으면 그 위치와 함께 PreconditionFailedError 반환
+/// 쓰기 batch와 별도로 먼저 조회하는 최선 노력(best-effort) 검사이며 원자적이지 않음:
+/// 조건은 여러 파티션에 걸친 필터라 조건부 쓰기(LWT)로 표현할 수 없으므로, 검사와 반영 사이에
+/// 다른 쓰기가 조건을 깨뜨려도 쓰기는 그대로 반영됨
+pub async fn check_preconditions(
+    tuple_store: &dyn TupleStore,
+    preconditions: &[Precondition],
+) -> SentinelResult<()> {
+    let violations: Vec<Violation> = preconditions.iter()
+        .enumerate()
+        .filter_map(|(index, precondition)| {
+            QueryPlan::for_filter(&precondition.filter)
+                .err()
+                .map(|e| Violation::new(index, "filter", e.to_string()))
+        })
+        .collect();
+    if !violations.is_empty() {
+        return Err(SentinelError::validation_errors("Invalid precondition filters", violations));
+    }
+
+    for (index, precondition) in preconditions.iter().enumerate() {
+        let matched = tuple_store.has_matching_tuple(&precondition.filter).await?;
+        match (precondition.operation, matched) {
+            (PreconditionOperation::MustMatch, false) => {
+                return Err(SentinelError::precondition_failed_error(
+                    index,
+                    format!("No tuple matches filter {:?}", precondition.filter),
+                ));
+            }
+            (PreconditionOperation::MustNotMatch, true) => {
+                return Err(SentinelError::precondition_failed_error(
+                    index,
+                    format!("A tuple matches filter {:?}", precondition.filter),
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    info!("{} preconditions satisfied", preconditions.len());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::RelationTupleFilter;
+    use crate::test_utils::MemoryTupleStore;
+
+    fn precondition(operation: PreconditionOperation, namespace: &str, object_id: &str, relation: &str) -> Precondition {
+        Precondition {
+            operation,
+            filter: RelationTupleFilter {
+                namespace: Some(namespace.to_string()),
+                object_id: (!object_id.is_empty()).then(|| object_id.to_string()),
+                relation: Some(relation.to_string()),
+                user_type: None,
+                user_id: None,
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_check_preconditions() {
+        let store = MemoryTupleStore::with_tuples(&[
+            "teams:backend#owner@user:alice",
+            "teams:backend#member@user:bob",
+        ]);
+
+        // 팀에 owner가 있고 admin이 없으면 통과
+        let satisfied = vec![
+            precondition(PreconditionOperation::MustMatch, "teams", "backend", "owner"),
+            precondition(PreconditionOperation::MustNotMatch, "teams", "backend", "admin"),
+        ];
+        check_preconditions(&store, &satisfied).await.unwrap();
+
+        // 실패한 조건의 위치를 반환
+        let failing = vec![
+            precondition(PreconditionOperation::MustMatch, "teams", "backend", "owner"),
+            precondition(PreconditionOperation::MustMatch, "teams", "frontend", "owner"),
+        ];
+        match check_preconditions(&store, &failing).await {
+            Err(SentinelError::PreconditionFailedError { index, .. }) => assert_eq!(index, 1),
+            other => panic!("expected precondition failure, got {:?}", other),
+        }
+
+        // 조회할 수 없는 필터는 검증 에러
+        let invalid = vec![precondition(PreconditionOperation::MustMatch, "teams", "", "owner"), Precondition {
+            operation: PreconditionOperation::MustNotMatch,
+            filter: RelationTupleFilter {
+                namespace: Some("teams".to_string()),
+                object_id: None,
+                relation: None,
+                user_type: None,
+                user_id: None,
+            },
+        }];
+        match check_preconditions(&store, &invalid).await {
+            Err(SentinelError::ValidationError { violations, .. }) => {
+                assert_eq!(violations.len(), 1);
+                assert_eq!(violations[0].index, 1);
+            }
+            other => panic!("expected validation error, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use crate::models::{RelationTuple, RelationTupleFilter};
+use crate::tuple_tables::{TupleTable, RELATION_TUPLES, USER_MEMBERSHIPS, RELATION_INDEX, SELECT_COLUMNS, tuple_field};
+use crate::errors::{SentinelError, SentinelResult};
+
+/// 선택 우선순위 순서의 조회 대상 테이블 (객체 하나의 파티션이 가장 작으므로 우선 사용)
+/// object_permissions는 relation_tuples와 키 구성이 같아 relation_tuples보다 나은 경우가 없으므로 제외
+const CANDIDATES: [&TupleTable; 3] = [&RELATION_TUPLES, &USER_MEMBERSHIPS, &RELATION_INDEX];
+
+/// RelationTupleFilter에 대한 조회 계획
+/// 필터의 모든 조건을 한 파티션 안의 조건으로 바꿀 수 있는 테이블을 선택
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryPlan {
+    /// 조회할 테이블 (sentinel 키스페이스)
+    pub table: &'static str,
+    /// WHERE 절의 (컬럼, 값) 조건 (파티션 키, 클러스터링 키 순)
+    pub restrictions: Vec<(&'static str, String)>,
+    /// 클러스터링 키 접두사가 아닌 조건이 있어 파티션 안에서 필터링이 필요한지
+    pub allow_filtering: bool,
+}
+
+impl QueryPlan {
+    /// 필터에 맞는 조회 계획 생성
+    /// user_id만 있고 user_type이 없으면 일반 사용자(user_type = "user")로 간주
+    /// 어떤 테이블의 파티션 키도 채울 수 없는 필터는 전체 스캔이 필요하므로 거부
+    pub fn for_filter(filter: &RelationTupleFilter) -> SentinelResult<Self> {
+        let user_type = filter.user_type.as_deref()
+            .or(filter.user_id.as_ref().map(|_| "user"));
+        let value = |column: &str| -> Option<&str> {
+            match column {
+                "namespace" => filter.namespace.as_deref(),
+                "object_id" => filter.object_id.as_deref(),
+                "relation" => filter.relation.as_deref(),
+                "user_type" => user_type,
+                "user_id" => filter.user_id.as_deref(),
+                _ => None,
+            }
+        };
+
+        let layout = CANDIDATES.iter()
+            .find(|layout| layout.partition_key.iter().all(|column| value(column).is_some()))
+            .ok_or_else(|| SentinelError::validation_error(
+                "Filter must specify (namespace, object_id), user_id, or (namespace, relation)",
+            ))?;
+
+        let mut restrictions = Vec::new();
+        for column in layout.key_columns() {
+            if let Some(value) = value(column) {
+                restrictions.push((column, value.to_string()));
+            }
+        }
+
+        // 클러스터링 조건이 키 앞부분부터 연속되지 않으면 ALLOW FILTERING 필요
+        let restricted: Vec<bool> = layout.clustering_key.iter().map(|column| value(column).is_some()).collect();
+        let allow_filtering = restricted.windows(2).any(|pair| !pair[0] && pair[1]);
+
+        Ok(Self {
+            table: layout.name,
+            restrictions,
+            allow_filtering,
+        })
+    }
+
+    /// 조회 CQL 생성 (값은 restrictions 순서대로 바인딩)
+    pub fn to_cql(&self) -> String {
+        let conditions: Vec<String> = self.restrictions.iter()
+            .map(|(column, _)| format!("{} = ?", column))
+            .collect();
+        format!(
+            "SELECT {} FROM sentinel.{} WHERE {}{}",
+            SELECT_COLUMNS,
+            self.table,
+            conditions.join(" AND "),
+            if self.allow_filtering { " ALLOW FILTERING" } else { "" },
+        )
+    }
+
+    /// 바인딩할 값 목록
+    pub fn values(&self) -> Vec<&str> {
+        self.restrictions.iter().map(|(_, value)| value.as_str()).collect()
+    }
+
+    /// 튜플이 계획의 모든 조건을 만족하는지 확인
+    pub fn matches(&self, tuple: &RelationTuple) -> bool {
+        self.restrictions.iter().all(|(column, value)| tuple_field(tuple, column) == value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(
+        namespace: Option<&str>,
+        object_id: Option<&str>,
+        relation: Option<&str>,
+        user_type: Option<&str>,
+        user_id: Option<&str>,
+    ) -> RelationTupleFilter {
+        RelationTupleFilter {
+            namespace: namespace.map(String::from),
+            object_id: object_id.map(String::from),
+            relation: relation.map(String::from),
+            user_type: user_type.map(String::from),
+            user_id: user_id.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_query_plan() {
+        // 객체 + 관계: 클러스터링 키 접두사
+        let plan = QueryPlan::for_filter(&filter(Some("documents"), Some("doc1"), Some("viewer"), None, None)).unwrap();
+        assert_eq!(plan.table, "relation_tuples");
+        assert!(!plan.allow_filtering);
+        assert_eq!(plan.values(), vec!["documents", "doc1", "viewer"]);
+
+        // 객체 + 사용자: 관계를 건너뛰므로 파티션 안에서 필터링
+        let plan = QueryPlan::for_filter(&filter(Some("documents"), Some("doc1"), None, None, Some("alice"))).unwrap();
+        assert_eq!(plan.table, "relation_tuples");
+        assert!(plan.allow_filtering);
+        assert!(plan.to_cql().ends_with("WHERE namespace = ? AND object_id = ? AND user_type = ? AND user_id = ? ALLOW FILTERING"));
+
+        // userset 주체가 가진 모든 권한
+        let plan = QueryPlan::for_filter(&filter(None, None, None, Some("userset"), Some("teams:backend#member"))).unwrap();
+        assert_eq!(plan.table, "user_memberships");
+        assert_eq!(plan.values(), vec!["teams:backend#member", "userset"]);
+
+        // 네임스페이스의 특정 관계 전체
+        let plan = QueryPlan::for_filter(&filter(Some("documents"), None, Some("owner"), None, None)).unwrap();
+        assert_eq!(plan.table, "relation_index");
+        assert!(!plan.allow_filtering);
+
+        let plan = QueryPlan::for_filter(&filter(Some("documents"), None, Some("owner"), Some("userset"), None)).unwrap();
+        assert_eq!(plan.table, "relation_index");
+        assert!(plan.allow_filtering);
+        assert!(plan.matches(&RelationTuple::new("documents", "doc1", "owner", "userset", "teams:a#member")));
+        assert!(!plan.matches(&RelationTuple::new("documents", "doc1", "owner", "user", "alice")));
+
+        // 전체 스캔이 필요한 필터는 거부
+        assert!(QueryPlan::for_filter(&filter(Some("documents"), None, None, None, None)).is_err());
+        assert!(QueryPlan::for_filter(&filter(None, None, Some("owner"), Some("user"), None)).is_err());
+    }
+}
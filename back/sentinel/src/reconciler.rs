@@ -0,0 +1,191 @@
+use std::sync::Arc;
+use std::time::Duration;
+use chrono::Utc;
+use futures::future::join_all;
+use tracing::{info, warn};
+use crate::models::{RelationTuple, ReconcileReport, DiscrepancyKind};
+use crate::tuple_store::TupleStore;
+use crate::tuple_tables::{TupleTable, RELATION_TUPLES, INDEX_TABLES};
+use crate::errors::SentinelResult;
+
+/// 전체 조회 시 한 번에 읽는 행 수
+const SCAN_PAGE_SIZE: u32 = 500;
+/// 이 시간보다 최근에 기록된 인덱스 행은 진행 중인 batch일 수 있으므로 원본이 없어도 삭제하지 않음
+const ORPHAN_GRACE_MILLIS: i64 = 5 * 60 * 1000;
+
+/// relation_tuples와 세 인덱스 테이블(user_memberships, object_permissions, relation_index)의 정합성 검사기
+/// relation_tuples를 기준으로 빠진 인덱스 행은 다시 쓰고, 원본 없이 남은 인덱스 행은 삭제
+/// 복구 직전에 원본 행을 다시 읽어, 검사하는 동안 삭제된 튜플의 인덱스 행을 되살리지 않음
+/// (다시 읽은 뒤 복구하기 전에 삭제된 튜플은 다음 검사에서 orphaned로 정리됨)
+pub struct IndexReconciler {
+    tuple_store: Arc<dyn TupleStore>,
+}
+
+impl IndexReconciler {
+    /// 새로운 IndexReconciler 생성
+    pub fn new(tuple_store: Arc<dyn TupleStore>) -> Self {
+        Self { tuple_store }
+    }
+
+    /// 전체 테이블을 검사하고 dry_run이 아니면 불일치를 복구
+    pub async fn reconcile(&self, dry_run: bool) -> SentinelResult<ReconcileReport> {
+        let mut report = ReconcileReport {
+            dry_run,
+            ..Default::default()
+        };
+
+        // 1. 원본 튜플마다 세 인덱스 행이 있는지 확인
+        let mut paging_state = None;
+        loop {
+            let page = self.tuple_store.scan_table(&RELATION_TUPLES, SCAN_PAGE_SIZE, paging_state).await?;
+            for tuple in &page.tuples {
+                report.scanned_tuples += 1;
+                self.reconcile_tuple(tuple, &mut report).await?;
+            }
+            match page.paging_state {
+                Some(state) => paging_state = Some(state),
+                None => break,
+            }
+        }
+
+        // 2. 인덱스 행마다 원본 튜플이 있는지 확인
+        let grace_cutoff = Utc::now().timestamp_millis() - ORPHAN_GRACE_MILLIS;
+        for table in INDEX_TABLES {
+            let mut paging_state = None;
+            loop {
+                let page = self.tuple_store.scan_table(table, SCAN_PAGE_SIZE, paging_state).await?;
+                for row in &page.tuples {
+                    report.scanned_index_rows += 1;
+                    self.reconcile_index_row(table, row, grace_cutoff, &mut report).await?;
+                }
+                match page.paging_state {
+                    Some(state) => paging_state = Some(state),
+                    None => break,
+                }
+            }
+        }
+
+        if report.missing + report.orphaned > 0 {
+            warn!(
+                "Index reconciliation found {} missing and {} orphaned rows ({} repaired)",
+                report.missing, report.orphaned, report.repaired
+            );
+        } else {
+            info!("Index reconciliation found no discrepancies in {} tuples", report.scanned_tuples);
+        }
+        Ok(report)
+    }
+
+    /// 조회한 원본 튜플의 빠진 인덱스 행 확인 및 복구
+    async fn reconcile_tuple(&self, scanned: &RelationTuple, report: &mut ReconcileReport) -> SentinelResult<()> {
+        // 만료된 튜플은 TTL로 모든 테이블에서 곧 삭제되므로 다시 쓰지 않음
+        if scanned.is_expired() {
+            return Ok(());
+        }
+        let rows = join_all(INDEX_TABLES.iter().map(|table| self.tuple_store.find_row(table, scanned))).await;
+        let mut missing = Vec::new();
+        for (table, row) in INDEX_TABLES.iter().zip(rows) {
+            if row?.is_none() {
+                missing.push(*table);
+            }
+        }
+        if missing.is_empty() {
+            return Ok(());
+        }
+
+        // 조회 이후 삭제되었거나 만료된 튜플은 불일치가 아님 (인덱스 행도 함께 삭제됨)
+        let Some(current) = self.tuple_store.find_row(&RELATION_TUPLES, scanned).await?
+            .filter(|tuple| !tuple.is_expired())
+        else {
+            return Ok(());
+        };
+        for table in missing {
+            report.record(table.name, DiscrepancyKind::Missing, &current);
+            if !report.dry_run {
+                self.tuple_store.write_row(table, &current).await?;
+                report.repaired += 1;
+            }
+        }
+        Ok(())
+    }
+
+    /// 원본 없이 남은 인덱스 행 확인 및 삭제
+    async fn reconcile_index_row(
+        &self,
+        table: &'static TupleTable,
+        row: &RelationTuple,
+        grace_cutoff: i64,
+        report: &mut ReconcileReport,
+    ) -> SentinelResult<()> {
+        if row.created_at.0 > grace_cutoff || self.tuple_store.find_row(&RELATION_TUPLES, row).await?.is_some() {
+            return Ok(());
+        }
+        report.record(table.name, DiscrepancyKind::Orphaned, row);
+        if !report.dry_run {
+            self.tuple_store.delete_row(table, row).await?;
+            report.repaired += 1;
+        }
+        Ok(())
+    }
+
+    /// 주기적으로 정합성을 검사하고 복구하는 루프
+    pub async fn run_reconcile_loop(self: Arc<Self>, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        // 시작 직후가 아니라 한 주기 뒤부터 실행
+        ticker.tick().await;
+        loop {
+            ticker.tick().await;
+            if let Err(e) = self.reconcile(false).await {
+                warn!("Failed to reconcile index tables: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scylla::value::CqlTimestamp;
+    use crate::test_utils::{parse_tuple, MemoryTupleStore};
+    use crate::tuple_tables::{USER_MEMBERSHIPS, RELATION_INDEX};
+
+    #[tokio::test]
+    async fn test_reconcile_missing_and_orphaned() {
+        let store = Arc::new(MemoryTupleStore::with_tuples(&[
+            "documents:doc1#viewer@user:alice",
+            "documents:doc2#editor@user:bob",
+        ]));
+        store.delete_row(&USER_MEMBERSHIPS, &parse_tuple("documents:doc1#viewer@user:alice")).await.unwrap();
+        let mut orphan = parse_tuple("documents:doc3#viewer@user:carol");
+        orphan.created_at = CqlTimestamp(0);
+        store.write_row(&RELATION_INDEX, &orphan).await.unwrap();
+        // 유예 시간 안의 인덱스 행은 진행 중인 batch일 수 있으므로 보고하지 않음
+        store.write_row(&RELATION_INDEX, &parse_tuple("documents:doc4#viewer@user:dave")).await.unwrap();
+
+        let reconciler = IndexReconciler::new(store.clone());
+        let report = reconciler.reconcile(true).await.unwrap();
+        assert_eq!((report.scanned_tuples, report.missing, report.orphaned, report.repaired), (2, 1, 1, 0));
+        assert_eq!(report.discrepancies.len(), 2);
+
+        let report = reconciler.reconcile(false).await.unwrap();
+        assert_eq!((report.missing, report.orphaned, report.repaired), (1, 1, 2));
+        assert!(store.find_row(&USER_MEMBERSHIPS, &parse_tuple("documents:doc1#viewer@user:alice")).await.unwrap().is_some());
+        assert!(store.find_row(&RELATION_INDEX, &orphan).await.unwrap().is_none());
+
+        let report = reconciler.reconcile(false).await.unwrap();
+        assert_eq!((report.missing, report.orphaned, report.repaired), (0, 0, 0));
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_tuple_deleted_during_scan() {
+        let store = Arc::new(MemoryTupleStore::new());
+        let reconciler = IndexReconciler::new(store.clone());
+
+        // 조회 이후 삭제된 튜플은 인덱스 행이 없어도 다시 쓰지 않음
+        let scanned = parse_tuple("documents:doc1#viewer@user:alice");
+        let mut report = ReconcileReport::default();
+        reconciler.reconcile_tuple(&scanned, &mut report).await.unwrap();
+        assert_eq!((report.missing, report.repaired), (0, 0));
+        assert!(store.find_row(&USER_MEMBERSHIPS, &scanned).await.unwrap().is_none());
+    }
+}
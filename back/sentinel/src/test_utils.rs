@@ -0,0 +1,426 @@
+//! 테스트용 인메모리 구현체 (캐시, 튜플 저장소, 네임스페이스 저장소)
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use chrono::Utc;
+use uuid::Uuid;
+use crate::cache::Cache;
+use crate::models::{RelationTuple, RelationTupleFilter, ChangelogEntry, ErasureReceipt, NamespaceRecord, Operation};
+use crate::namespace_config::NamespaceConfig;
+use crate::namespace_store::NamespaceStore;
+use crate::tuple_store::{TupleStore, TuplePage};
+use crate::tuple_tables::{TupleTable, RELATION_TUPLES, INDEX_TABLES};
+use crate::query_plan::QueryPlan;
+use crate::errors::{SentinelError, SentinelResult};
+
+/// 테스트용 간단한 캐시 구현
+pub struct MockCache {
+    data: Arc<Mutex<HashMap<String, (String, i64)>>>,
+}
+
+impl MockCache {
+    pub fn new() -> Self {
+        Self {
+            data: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Cache for MockCache {
+    async fn get(&self, key: &str) -> SentinelResult<Option<String>> {
+        let data = self.data.lock().unwrap();
+        if let Some((value, expiry)) = data.get(key) {
+            if Utc::now().timestamp() < *expiry {
+                Ok(Some(value.clone()))
+            } else {
+                Ok(None)
+            }
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn set(&self, key: &str, value: &str, ttl_seconds: u64) -> SentinelResult<()> {
+        let expiry = Utc::now().timestamp() + ttl_seconds as i64;
+        let mut data = self.data.lock().unwrap();
+        data.insert(key.to_string(), (value.to_string(), expiry));
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> SentinelResult<()> {
+        self.data.lock().unwrap().remove(key);
+        Ok(())
+    }
+
+    async fn delete_pattern(&self, _pattern: &str) -> SentinelResult<()> {
+        Ok(())
+    }
+
+    async fn ping(&self) -> SentinelResult<()> {
+        Ok(())
+    }
+}
+
+/// 테스트용 인메모리 튜플 저장소
+/// 인덱스 테이블은 tuples(relation_tuples)를 그대로 따르며, write_row/delete_row로
+/// 테이블 하나만 바꾸면 그 행만 index_drift에 따로 보관 (None이면 행 없음)
+#[derive(Default)]
+pub struct MemoryTupleStore {
+    tuples: Mutex<Vec<RelationTuple>>,
+    index_drift: Mutex<HashMap<(&'static str, String), Option<RelationTuple>>>,
+    claims: Mutex<HashMap<String, Uuid>>,
+    changelog: Mutex<Vec<ChangelogEntry>>,
+    erasures: Mutex<Vec<ErasureReceipt>>,
+}
+
+impl MemoryTupleStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// "namespace:object_id#relation@user_type:user_id" 형식의 튜플들로 저장소 생성
+    pub fn with_tuples(tuples: &[&str]) -> Self {
+        let store = Self::new();
+        store.tuples.lock().unwrap().extend(tuples.iter().map(|t| parse_tuple(t)));
+        store
+    }
+
+    /// 조건에 맞고 만료되지 않은 튜플 조회 (Scylla 구현처럼 만료된 튜플 제외)
+    fn find(&self, predicate: impl Fn(&RelationTuple) -> bool) -> Vec<RelationTuple> {
+        self.tuples.lock().unwrap().iter().filter(|t| predicate(t) && !t.is_expired()).cloned().collect()
+    }
+
+    /// 테이블 하나의 모든 행 (만료된 행 포함)
+    fn table_rows(&self, table: &TupleTable) -> Vec<RelationTuple> {
+        let tuples = self.tuples.lock().unwrap().clone();
+        if table.name == RELATION_TUPLES.name {
+            return tuples;
+        }
+        let drift = self.index_drift.lock().unwrap();
+        let mut rows: Vec<RelationTuple> = tuples.into_iter()
+            .filter(|t| !drift.contains_key(&(table.name, tuple_key(t))))
+            .collect();
+        rows.extend(drift.iter()
+            .filter(|((name, _), _)| *name == table.name)
+            .filter_map(|(_, row)| row.clone()));
+        rows
+    }
+
+    /// 모든 테이블에 함께 반영되는 변경 (테이블별로 따로 보관하던 행을 버림)
+    fn clear_drift(&self, tuple: &RelationTuple) {
+        let key = tuple_key(tuple);
+        self.index_drift.lock().unwrap().retain(|(_, drifted), _| *drifted != key);
+    }
+
+    /// relation_tuples만 바뀌기 전에 인덱스 테이블의 현재 행을 따로 보관
+    fn freeze_indexes(&self, tuple: &RelationTuple) {
+        let current = self.tuples.lock().unwrap().iter().find(|t| same_tuple(t, tuple)).cloned();
+        let mut drift = self.index_drift.lock().unwrap();
+        for table in INDEX_TABLES {
+            drift.entry((table.name, tuple_key(tuple))).or_insert_with(|| current.clone());
+        }
+    }
+}
+
+/// 튜플 키 순으로 정렬한 행의 한 페이지, paging state는 마지막으로 반환한 튜플의 키
+fn page_by_key(mut rows: Vec<RelationTuple>, page_size: u32, paging_state: Option<Vec<u8>>) -> SentinelResult<TuplePage> {
+    rows.sort_by_key(tuple_key);
+
+    let after = paging_state
+        .map(|bytes| String::from_utf8(bytes).map_err(|_| SentinelError::validation_error("Invalid paging state")))
+        .transpose()?;
+    let mut remaining: Vec<RelationTuple> = rows.into_iter()
+        .filter(|t| after.as_ref().is_none_or(|after| &tuple_key(t) > after))
+        .collect();
+
+    let has_more = remaining.len() > page_size as usize;
+    remaining.truncate(page_size as usize);
+    let paging_state = match remaining.last() {
+        Some(last) if has_more => Some(tuple_key(last).into_bytes()),
+        _ => None,
+    };
+    Ok(TuplePage { tuples: remaining, paging_state })
+}
+
+/// "namespace:object_id#relation@user_type:user_id" 형식 파싱
+/// userset 주체는 "...@userset:teams:backend#member" 형식으로 작성
+pub fn parse_tuple(tuple: &str) -> RelationTuple {
+    let (object, subject) = tuple.split_once('@').expect("missing '@'");
+    let (namespace, object_relation) = object.split_once(':').expect("missing ':'");
+    let (object_id, relation) = object_relation.split_once('#').expect("missing '#'");
+    let (user_type, user_id) = subject.split_once(':').expect("missing subject ':'");
+    RelationTuple::new(namespace, object_id, relation, user_type, user_id)
+}
+
+/// 정렬과 paging state에 쓰는 튜플 키
+fn tuple_key(tuple: &RelationTuple) -> String {
+    [&tuple.namespace, &tuple.object_id, &tuple.relation, &tuple.user_type, &tuple.user_id]
+        .map(String::as_str)
+        .join("\u{0}")
+}
+
+fn same_tuple(a: &RelationTuple, b: &RelationTuple) -> bool {
+    a.namespace == b.namespace
+        && a.object_id == b.object_id
+        && a.relation == b.relation
+        && a.user_type == b.user_type
+        && a.user_id == b.user_id
+}
+
+#[async_trait::async_trait]
+impl TupleStore for MemoryTupleStore {
+    async fn insert_tuple(&self, tuple: &RelationTuple) -> SentinelResult<()> {
+        self.clear_drift(tuple);
+        let mut tuples = self.tuples.lock().unwrap();
+        tuples.retain(|t| !same_tuple(t, tuple));
+        tuples.push(tuple.clone());
+        Ok(())
+    }
+
+    async fn delete_tuple(&self, tuple: &RelationTuple) -> SentinelResult<()> {
+        self.clear_drift(tuple);
+        self.tuples.lock().unwrap().retain(|t| !same_tuple(t, tuple));
+        Ok(())
+    }
+
+    async fn write_tuples(&self, updates: &[(Operation, RelationTuple)]) -> SentinelResult<()> {
+        let mut tuples = self.tuples.lock().unwrap();
+        let mut changelog = self.changelog.lock().unwrap();
+        for (operation, tuple) in updates {
+            self.clear_drift(tuple);
+            tuples.retain(|t| !same_tuple(t, tuple));
+            if !matches!(operation, Operation::Delete) {
+                tuples.push(tuple.clone());
+            }
+            changelog.push(ChangelogEntry::new(tuple, operation));
+        }
+        Ok(())
+    }
+
+    /// 선점은 만료되지 않음 (release_tuple로만 해제)
+    async fn claim_tuple(&self, tuple: &RelationTuple, claim_id: Uuid) -> SentinelResult<bool> {
+        let mut claims = self.claims.lock().unwrap();
+        if claims.contains_key(&tuple_key(tuple)) {
+            return Ok(false);
+        }
+        claims.insert(tuple_key(tuple), claim_id);
+        Ok(true)
+    }
+
+    async fn release_tuple(&self, tuple: &RelationTuple, claim_id: Uuid) -> SentinelResult<()> {
+        self.claims.lock().unwrap().retain(|key, claimed| !(*key == tuple_key(tuple) && *claimed == claim_id));
+        Ok(())
+    }
+
+    async fn find_direct_tuple(&self, tuple: &RelationTuple) -> SentinelResult<Option<RelationTuple>> {
+        Ok(self.find(|t| same_tuple(t, tuple)).into_iter().next())
+    }
+
+    async fn find_tuples_by_object(
+        &self,
+        namespace: &str,
+        object_id: &str,
+    ) -> SentinelResult<Vec<RelationTuple>> {
+        Ok(self.find(|t| t.namespace == namespace && t.object_id == object_id))
+    }
+
+    async fn find_tuples_by_object_relation(
+        &self,
+        namespace: &str,
+        object_id: &str,
+        relation: &str,
+    ) -> SentinelResult<Vec<RelationTuple>> {
+        Ok(self.find(|t| t.namespace == namespace && t.object_id == object_id && t.relation == relation))
+    }
+
+    async fn find_user_memberships(&self, user_id: &str) -> SentinelResult<Vec<RelationTuple>> {
+        self.find_subject_memberships("user", user_id).await
+    }
+
+    async fn find_subject_memberships(&self, user_type: &str, user_id: &str) -> SentinelResult<Vec<RelationTuple>> {
+        Ok(self.find(|t| t.user_type == user_type && t.user_id == user_id))
+    }
+
+    async fn find_userset_members(
+        &self,
+        namespace: &str,
+        object_id: &str,
+        relation: &str,
+    ) -> SentinelResult<Vec<RelationTuple>> {
+        self.find_tuples_by_object_relation(namespace, object_id, relation).await
+    }
+
+    async fn stored_relations(&self, namespace: &str) -> SentinelResult<Vec<String>> {
+        let mut relations: Vec<String> = self.find(|t| t.namespace == namespace)
+            .into_iter()
+            .map(|t| t.relation)
+            .collect();
+        relations.sort();
+        relations.dedup();
+        Ok(relations)
+    }
+
+    /// 튜플 키 순으로 정렬해 조회하며, paging state는 마지막으로 반환한 튜플의 키
+    /// (Scylla처럼 페이지 사이에 튜플이 삭제되어도 건너뛰는 튜플이 없음)
+    async fn read_tuples(
+        &self,
+        filter: &RelationTupleFilter,
+        page_size: u32,
+        paging_state: Option<Vec<u8>>,
+    ) -> SentinelResult<TuplePage> {
+        let plan = QueryPlan::for_filter(filter)?;
+        page_by_key(self.find(|t| plan.matches(t)), page_size, paging_state)
+    }
+
+    async fn scan_table(
+        &self,
+        table: &'static TupleTable,
+        page_size: u32,
+        paging_state: Option<Vec<u8>>,
+    ) -> SentinelResult<TuplePage> {
+        page_by_key(self.table_rows(table), page_size, paging_state)
+    }
+
+    async fn find_row(&self, table: &'static TupleTable, tuple: &RelationTuple) -> SentinelResult<Option<RelationTuple>> {
+        Ok(self.table_rows(table).into_iter().find(|t| same_tuple(t, tuple)))
+    }
+
+    async fn write_row(&self, table: &'static TupleTable, tuple: &RelationTuple) -> SentinelResult<()> {
+        if table.name == RELATION_TUPLES.name {
+            self.freeze_indexes(tuple);
+            let mut tuples = self.tuples.lock().unwrap();
+            tuples.retain(|t| !same_tuple(t, tuple));
+            tuples.push(tuple.clone());
+        } else {
+            self.index_drift.lock().unwrap().insert((table.name, tuple_key(tuple)), Some(tuple.clone()));
+        }
+        Ok(())
+    }
+
+    async fn delete_row(&self, table: &'static TupleTable, tuple: &RelationTuple) -> SentinelResult<()> {
+        if table.name == RELATION_TUPLES.name {
+            self.freeze_indexes(tuple);
+            self.tuples.lock().unwrap().retain(|t| !same_tuple(t, tuple));
+        } else {
+            self.index_drift.lock().unwrap().insert((table.name, tuple_key(tuple)), None);
+        }
+        Ok(())
+    }
+
+    async fn record_change(&self, entry: &ChangelogEntry) -> SentinelResult<()> {
+        self.changelog.lock().unwrap().push(entry.clone());
+        Ok(())
+    }
+
+    async fn read_changes(&self, namespace: &str, after_revision: i64, limit: usize) -> SentinelResult<Vec<ChangelogEntry>> {
+        let mut entries: Vec<ChangelogEntry> = self.changelog.lock().unwrap().iter()
+            .filter(|entry| entry.namespace == namespace && entry.revision > after_revision)
+            .cloned()
+            .collect();
+        entries.sort_by_key(|entry| entry.revision);
+        entries.truncate(limit);
+        Ok(entries)
+    }
+
+    async fn pseudonymize_changes(
+        &self,
+        namespaces: &[String],
+        user_type: &str,
+        user_id: &str,
+        pseudonym: &str,
+    ) -> SentinelResult<u64> {
+        let mut pseudonymized = 0;
+        for entry in self.changelog.lock().unwrap().iter_mut() {
+            if namespaces.contains(&entry.namespace) && entry.user_type == user_type && entry.user_id == user_id {
+                entry.user_id = pseudonym.to_string();
+                pseudonymized += 1;
+            }
+        }
+        Ok(pseudonymized)
+    }
+
+    async fn record_erasure(&self, receipt: &ErasureReceipt) -> SentinelResult<()> {
+        self.erasures.lock().unwrap().push(receipt.clone());
+        Ok(())
+    }
+
+    async fn find_erasure(&self, receipt_id: Uuid) -> SentinelResult<Option<ErasureReceipt>> {
+        Ok(self.erasures.lock().unwrap().iter().find(|r| r.receipt_id == receipt_id).cloned())
+    }
+}
+
+/// 테스트용 인메모리 네임스페이스 설정 저장소
+#[derive(Default)]
+pub struct MemoryNamespaceStore {
+    records: Mutex<HashMap<String, NamespaceRecord>>,
+    versions: Mutex<Vec<NamespaceRecord>>,
+}
+
+impl MemoryNamespaceStore {
+    /// JSON 설정들로 저장소 생성
+    pub fn from_json(configs: &[&str]) -> Self {
+        let store = Self::default();
+        for json in configs {
+            store.put(NamespaceConfig::from_json(json).expect("invalid test namespace config"));
+        }
+        store
+    }
+
+    /// 설정 추가 또는 교체 (버전 증가)
+    pub fn put(&self, config: NamespaceConfig) {
+        let mut records = self.records.lock().unwrap();
+        let version = records.get(&config.name).map_or(1, |r| r.version + 1);
+        let record = NamespaceRecord {
+            config,
+            version,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        self.versions.lock().unwrap().push(record.clone());
+        records.insert(record.config.name.clone(), record);
+    }
+
+    /// 설정 삭제
+    pub fn remove(&self, name: &str) {
+        self.records.lock().unwrap().remove(name);
+    }
+}
+
+#[async_trait::async_trait]
+impl NamespaceStore for MemoryNamespaceStore {
+    async fn find_namespace(&self, name: &str) -> SentinelResult<Option<NamespaceRecord>> {
+        Ok(self.records.lock().unwrap().get(name).cloned())
+    }
+
+    async fn list_namespaces(&self) -> SentinelResult<Vec<NamespaceRecord>> {
+        Ok(self.records.lock().unwrap().values().cloned().collect())
+    }
+
+    async fn find_namespace_version(&self, name: &str, version: i32) -> SentinelResult<Option<NamespaceRecord>> {
+        Ok(self.versions.lock().unwrap().iter()
+            .find(|r| r.config.name == name && r.version == version)
+            .cloned())
+    }
+
+    async fn create_namespace(&self, config: &NamespaceConfig) -> SentinelResult<()> {
+        if self.records.lock().unwrap().contains_key(&config.name) {
+            return Err(SentinelError::conflict_error(format!("Namespace '{}' already exists", config.name)));
+        }
+        self.put(config.clone());
+        Ok(())
+    }
+
+    async fn update_namespace(&self, config: &NamespaceConfig, current_version: i32) -> SentinelResult<()> {
+        let version = self.records.lock().unwrap().get(&config.name).map(|r| r.version);
+        if version != Some(current_version) {
+            return Err(SentinelError::conflict_error(format!("Namespace '{}' was modified concurrently", config.name)));
+        }
+        self.put(config.clone());
+        Ok(())
+    }
+
+    async fn delete_namespace(&self, name: &str) -> SentinelResult<bool> {
+        Ok(self.records.lock().unwrap().remove(name).is_some())
+    }
+}
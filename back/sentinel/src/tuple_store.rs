@@ -1,11 +1,39 @@
 use std::sync::Arc;
 use scylla::client::session::Session;
-use scylla::value::CqlTimestamp;
-use crate::models::{RelationTuple, ChangelogEntry, Operation};
+use scylla::statement::Statement;
+use scylla::statement::batch::{Batch, BatchType};
+use scylla::response::{PagingState, PagingStateResponse};
+use scylla::value::{CqlTimestamp, CqlValue};
+use chrono::Utc;
+use futures::future::join_all;
+use uuid::Uuid;
+use crate::models::{
+    RelationTuple, RelationTupleFilter, ChangelogEntry, ErasureReceipt, Operation, changelog_bucket, CHANGELOG_RETENTION_MICROS,
+};
+use crate::query_plan::QueryPlan;
+use crate::tuple_tables::{TupleTable, TUPLE_TABLES, TUPLE_CLAIMS};
+use crate::namespace_store::lwt_applied;
 use crate::errors::{SentinelError, SentinelResult};
 
+/// 존재 여부 확인 시 한 번에 읽는 튜플 수
+const MATCH_PAGE_SIZE: u32 = 100;
+/// 변경 이력 전체 조회 시 한 번에 읽는 행 수
+const CHANGELOG_SCAN_PAGE_SIZE: i32 = 500;
+
+/// user_memberships 조회 결과 (user_id, user_type, namespace, object_id, relation, created_at, expires_at, caveat)
+type SubjectMembershipRow = (String, String, String, String, String, CqlTimestamp, Option<CqlTimestamp>, Option<String>);
+
+/// 페이지 단위 튜플 조회 결과
+#[derive(Debug, Clone)]
+pub struct TuplePage {
+    pub tuples: Vec<RelationTuple>,
+    /// 다음 페이지를 이어서 읽기 위한 저장소 커서 (없으면 마지막 페이지)
+    pub paging_state: Option<Vec<u8>>,
+}
+
 /// ScyllaDB와의 상호작용을 위한 TupleStore trait
 /// 권한 튜플의 CRUD 작업과 복잡한 쿼리를 담당
+/// 튜플 조회 메서드는 만료 시간(expires_at)이 지난 튜플을 반환하지 않음
 #[async_trait::async_trait]
 pub trait TupleStore: Send + Sync {
     /// 권한 튜플 삽입
@@ -13,7 +41,18 @@ pub trait TupleStore: Send + Sync {
     
     /// 권한 튜플 삭제  
     async fn delete_tuple(&self, tuple: &RelationTuple) -> SentinelResult<()>;
+
+    /// 여러 튜플 변경을 한 번에 반영 (모두 반영되거나 아무것도 반영되지 않음)
+    /// Delete가 아닌 작업은 모두 삽입으로 기록하므로 존재 여부에 따른 처리는 호출 전에 결정 (plan_updates)
+    async fn write_tuples(&self, updates: &[(Operation, RelationTuple)]) -> SentinelResult<()>;
     
+    /// 선점 테이블(tuple_claims)에 같은 튜플의 선점이 없을 때만 선점 (Create 중복 방지용)
+    /// 다른 쓰기가 선점 중이면 false. 튜플 테이블은 바꾸지 않으며, 선점은 CLAIM_TTL_SECS 뒤 자동으로 해제됨
+    async fn claim_tuple(&self, tuple: &RelationTuple, claim_id: Uuid) -> SentinelResult<bool>;
+
+    /// claim_tuple로 선점한 튜플 해제 (claim_id가 다른 선점은 그대로 둠)
+    async fn release_tuple(&self, tuple: &RelationTuple, claim_id: Uuid) -> SentinelResult<()>;
+
     /// 직접 권한 튜플 조회 (정확히 일치하는 튜플)
     async fn find_direct_tuple(&self, tuple: &RelationTuple) -> SentinelResult<Option<RelationTuple>>;
     
@@ -34,6 +73,10 @@ pub trait TupleStore: Send + Sync {
     
     /// 사용자의 그룹 멤버십 조회 (team:backend#member@user:alice 형태)
     async fn find_user_memberships(&self, user_id: &str) -> SentinelResult<Vec<RelationTuple>>;
+
+    /// 특정 주체가 저장된 모든 튜플 조회 (user_memberships 인덱스)
+    /// 예: ("userset", "teams:backend#member"), ("folders", "shared")
+    async fn find_subject_memberships(&self, user_type: &str, user_id: &str) -> SentinelResult<Vec<RelationTuple>>;
     
     /// 특정 userset의 모든 멤버 조회 (team:backend#member에 속한 모든 사용자)
     async fn find_userset_members(
@@ -43,8 +86,102 @@ pub trait TupleStore: Send + Sync {
         relation: &str,
     ) -> SentinelResult<Vec<RelationTuple>>;
     
-    /// 변경 이력 기록
+    /// 네임스페이스에 튜플이 저장된 관계 목록 (정렬, 중복 없음)
+    /// 네임스페이스 설정 등록/수정/삭제 시 고아 튜플 확인에 사용
+    async fn stored_relations(&self, namespace: &str) -> SentinelResult<Vec<String>>;
+
+    /// 필터에 맞는 튜플을 최대 page_size개 조회
+    /// paging_state는 이전 페이지의 TuplePage::paging_state (None이면 처음부터)
+    async fn read_tuples(
+        &self,
+        filter: &RelationTupleFilter,
+        page_size: u32,
+        paging_state: Option<Vec<u8>>,
+    ) -> SentinelResult<TuplePage>;
+
+    /// 테이블 하나의 행을 최대 page_size개 조회 (인덱스 정합성 검사용, 만료된 행 포함)
+    /// paging_state는 이전 페이지의 TuplePage::paging_state (None이면 처음부터)
+    async fn scan_table(
+        &self,
+        table: &'static TupleTable,
+        page_size: u32,
+        paging_state: Option<Vec<u8>>,
+    ) -> SentinelResult<TuplePage>;
+
+    /// 테이블 하나에서 튜플 키의 행 조회 (인덱스 정합성 검사용, 만료된 행 포함)
+    async fn find_row(&self, table: &'static TupleTable, tuple: &RelationTuple) -> SentinelResult<Option<RelationTuple>>;
+
+    /// 테이블 하나에만 행 기록 (인덱스 복구용, 변경 이력 없음)
+    async fn write_row(&self, table: &'static TupleTable, tuple: &RelationTuple) -> SentinelResult<()>;
+
+    /// 테이블 하나에서만 행 삭제 (인덱스 복구용, 변경 이력 없음)
+    async fn delete_row(&self, table: &'static TupleTable, tuple: &RelationTuple) -> SentinelResult<()>;
+
+    /// 필터에 맞는 튜플이 하나라도 있는지 확인
+    /// 파티션 안 필터링 조회는 빈 페이지 뒤에 결과가 있을 수 있으므로 찾거나 끝날 때까지 페이지를 넘김
+    async fn has_matching_tuple(&self, filter: &RelationTupleFilter) -> SentinelResult<bool> {
+        let mut paging_state = None;
+        loop {
+            let page = self.read_tuples(filter, MATCH_PAGE_SIZE, paging_state).await?;
+            if !page.tuples.is_empty() {
+                return Ok(true);
+            }
+            match page.paging_state {
+                Some(state) => paging_state = Some(state),
+                None => return Ok(false),
+            }
+        }
+    }
+
+    /// 변경 이력 하나만 따로 기록 (튜플 변경의 이력은 write_tuples가 같은 batch에 기록)
     async fn record_change(&self, entry: &ChangelogEntry) -> SentinelResult<()>;
+
+    /// 네임스페이스에서 after_revision 이후의 변경 이력을 revision 순으로 최대 limit개 조회
+    async fn read_changes(&self, namespace: &str, after_revision: i64, limit: usize) -> SentinelResult<Vec<ChangelogEntry>>;
+
+    /// 네임스페이스들의 보관 기간 안 변경 이력에서 주체의 user_id를 pseudonym으로 교체하고 교체한 이력 수 반환
+    async fn pseudonymize_changes(
+        &self,
+        namespaces: &[String],
+        user_type: &str,
+        user_id: &str,
+        pseudonym: &str,
+    ) -> SentinelResult<u64>;
+
+    /// 사용자 삭제 영수증 저장
+    async fn record_erasure(&self, receipt: &ErasureReceipt) -> SentinelResult<()>;
+
+    /// 사용자 삭제 영수증 조회
+    async fn find_erasure(&self, receipt_id: Uuid) -> SentinelResult<Option<ErasureReceipt>>;
+}
+
+/// 튜플 변경 하나를 네 테이블에 반영하는 CQL 문장과 바인딩 값
+fn tuple_mutations(operation: &Operation, tuple: &RelationTuple) -> Vec<(String, Vec<CqlValue>)> {
+    TUPLE_TABLES.iter()
+        .map(|table| match operation {
+            Operation::Delete => table.delete(tuple),
+            _ => table.insert(tuple),
+        })
+        .collect()
+}
+
+/// 변경 이력 기록 CQL 문장과 바인딩 값
+fn changelog_mutation(entry: &ChangelogEntry) -> (String, Vec<CqlValue>) {
+    (
+        "INSERT INTO sentinel.changelog_by_namespace (namespace, bucket, revision, id, object_id, relation, user_type, user_id, operation, timestamp) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)".to_string(),
+        vec![
+            CqlValue::Text(entry.namespace.clone()),
+            CqlValue::BigInt(entry.bucket),
+            CqlValue::BigInt(entry.revision),
+            CqlValue::Uuid(entry.id),
+            CqlValue::Text(entry.object_id.clone()),
+            CqlValue::Text(entry.relation.clone()),
+            CqlValue::Text(entry.user_type.clone()),
+            CqlValue::Text(entry.user_id.clone()),
+            CqlValue::Text(entry.operation.clone()),
+            CqlValue::Timestamp(entry.timestamp),
+        ],
+    )
 }
 
 /// ScyllaDB 기반 TupleStore 구현체
@@ -60,145 +197,65 @@ impl ScyllaTupleStore {
 
 #[async_trait::async_trait]
 impl TupleStore for ScyllaTupleStore {
-    /// 권한 튜플 삽입 (인덱스 테이블들에 동시 삽입)
+    /// 권한 튜플 삽입 (네 테이블과 변경 이력을 하나의 batch로 기록)
     async fn insert_tuple(&self, tuple: &RelationTuple) -> SentinelResult<()> {
-        // 메인 테이블에 삽입
-        let main_query = "
-            INSERT INTO sentinel.relation_tuples 
-            (namespace, object_id, relation, user_type, user_id, created_at)
-            VALUES (?, ?, ?, ?, ?, ?)
-        ";
-        
-        self.session
-            .query_unpaged(main_query, tuple)
-            .await
-            .map_err(|e| SentinelError::from_scylla_error(e, "Failed to insert tuple"))?;
-        
-        // 인덱스 테이블들에도 삽입
-        let user_membership_query = "
-            INSERT INTO sentinel.user_memberships 
-            (user_id, user_type, namespace, object_id, relation, created_at)
-            VALUES (?, ?, ?, ?, ?, ?)
-        ";
-        
-        let user_membership_values = (
-            &tuple.user_id, &tuple.user_type, &tuple.namespace,
-            &tuple.object_id, &tuple.relation, &tuple.created_at
-        );
-        
-        self.session
-            .query_unpaged(user_membership_query, user_membership_values)
-            .await
-            .map_err(|e| SentinelError::from_scylla_error(e, "Failed to insert user membership"))?;
-        
-        let object_permission_query = "
-            INSERT INTO sentinel.object_permissions 
-            (namespace, object_id, relation, user_type, user_id, created_at)
-            VALUES (?, ?, ?, ?, ?, ?)
-        ";
-        
-        self.session
-            .query_unpaged(object_permission_query, tuple)
-            .await
-            .map_err(|e| SentinelError::from_scylla_error(e, "Failed to insert object permission"))?;
-        
-        let relation_index_query = "
-            INSERT INTO sentinel.relation_index 
-            (namespace, relation, object_id, user_type, user_id, created_at)
-            VALUES (?, ?, ?, ?, ?, ?)
-        ";
-        
-        let relation_index_values = (
-            &tuple.namespace, &tuple.relation, &tuple.object_id,
-            &tuple.user_type, &tuple.user_id, &tuple.created_at
-        );
-        
-        self.session
-            .query_unpaged(relation_index_query, relation_index_values)
-            .await
-            .map_err(|e| SentinelError::from_scylla_error(e, "Failed to insert relation index"))?;
-            
-        // 변경 이력 기록
-        let changelog = ChangelogEntry::new(tuple, &Operation::Insert);
-        self.record_change(&changelog).await?;
-        
-        Ok(())
+        self.write_tuples(&[(Operation::Insert, tuple.clone())]).await
     }
     
-    /// 권한 튜플 삭제 (모든 인덱스 테이블에서 삭제)
+    /// 권한 튜플 삭제 (네 테이블에서 삭제하고 변경 이력을 하나의 batch로 기록)
     async fn delete_tuple(&self, tuple: &RelationTuple) -> SentinelResult<()> {
-        let tuple_values = (
-            &tuple.namespace, &tuple.object_id, &tuple.relation,
-            &tuple.user_type, &tuple.user_id,
-        );
-        
-        // 메인 테이블에서 삭제
-        let main_delete = "
-            DELETE FROM sentinel.relation_tuples 
-            WHERE namespace = ? AND object_id = ? 
-            AND relation = ? AND user_type = ? AND user_id = ?
-        ";
-        
-        self.session
-            .query_unpaged(main_delete, tuple_values)
-            .await
-            .map_err(|e| SentinelError::from_scylla_error(e, "Failed to delete tuple"))?;
-        
-        // 인덱스 테이블들에서도 삭제
-        let user_membership_delete = "
-            DELETE FROM sentinel.user_memberships 
-            WHERE user_id = ? AND user_type = ? 
-            AND namespace = ? AND object_id = ? AND relation = ?
-        ";
-        
-        let user_membership_values = (
-            &tuple.user_id, &tuple.user_type, &tuple.namespace,
-            &tuple.object_id, &tuple.relation,
-        );
-        
+        self.write_tuples(&[(Operation::Delete, tuple.clone())]).await
+    }
+    
+    /// 여러 튜플 변경을 하나의 logged batch로 반영
+    /// 네 테이블의 변경과 변경 이력이 모두 함께 적용되거나 모두 적용되지 않음
+    async fn write_tuples(&self, updates: &[(Operation, RelationTuple)]) -> SentinelResult<()> {
+        if updates.is_empty() {
+            return Ok(());
+        }
+
+        let mut batch = Batch::new(BatchType::Logged);
+        let mut values = Vec::new();
+        for (operation, tuple) in updates {
+            let changelog = ChangelogEntry::new(tuple, operation);
+            for (query, row) in tuple_mutations(operation, tuple).into_iter().chain([changelog_mutation(&changelog)]) {
+                batch.append_statement(query.as_str());
+                values.push(row);
+            }
+        }
+
         self.session
-            .query_unpaged(user_membership_delete, user_membership_values)
+            .batch(&batch, values)
             .await
-            .map_err(|e| SentinelError::from_scylla_error(e, "Failed to delete user membership"))?;
-        
-        let object_permission_delete = "
-            DELETE FROM sentinel.object_permissions 
-            WHERE namespace = ? AND object_id = ? 
-            AND relation = ? AND user_type = ? AND user_id = ?
-        ";
-        
-        self.session
-            .query_unpaged(object_permission_delete, tuple_values)
+            .map_err(|e| SentinelError::from_scylla_error(e, "Failed to write tuples"))?;
+
+        Ok(())
+    }
+
+    /// tuple_claims에 INSERT ... IF NOT EXISTS (LWT)
+    async fn claim_tuple(&self, tuple: &RelationTuple, claim_id: Uuid) -> SentinelResult<bool> {
+        let (query, values) = TUPLE_CLAIMS.insert_claim(tuple, claim_id);
+        let result = self.session
+            .query_unpaged(query, values)
             .await
-            .map_err(|e| SentinelError::from_scylla_error(e, "Failed to delete object permission"))?;
-        
-        let relation_index_delete = "
-            DELETE FROM sentinel.relation_index 
-            WHERE namespace = ? AND relation = ? 
-            AND object_id = ? AND user_type = ? AND user_id = ?
-        ";
-        
-        let relation_index_values = (
-            &tuple.namespace, &tuple.relation, &tuple.object_id,
-            &tuple.user_type, &tuple.user_id,
-        );
-        
+            .map_err(|e| SentinelError::from_scylla_error(e, "Failed to claim tuple"))?;
+        lwt_applied(result)
+    }
+
+    /// tuple_claims에서 DELETE ... IF claim_id = ? (LWT)
+    async fn release_tuple(&self, tuple: &RelationTuple, claim_id: Uuid) -> SentinelResult<()> {
+        let (query, values) = TUPLE_CLAIMS.delete_claim(tuple, claim_id);
         self.session
-            .query_unpaged(relation_index_delete, relation_index_values)
+            .query_unpaged(query, values)
             .await
-            .map_err(|e| SentinelError::from_scylla_error(e, "Failed to delete relation index"))?;
-            
-        // 변경 이력 기록
-        let changelog = ChangelogEntry::new(tuple, &Operation::Delete);
-        self.record_change(&changelog).await?;
-        
+            .map_err(|e| SentinelError::from_scylla_error(e, "Failed to release tuple"))?;
         Ok(())
     }
-    
+
     /// 직접 권한 튜플 조회
     async fn find_direct_tuple(&self, tuple: &RelationTuple) -> SentinelResult<Option<RelationTuple>> {
         let query = "
-            SELECT namespace, object_id, relation, user_type, user_id, created_at
+            SELECT namespace, object_id, relation, user_type, user_id, created_at, expires_at, caveat
             FROM sentinel.relation_tuples 
             WHERE namespace = ? AND object_id = ? 
             AND relation = ? AND user_type = ? AND user_id = ?
@@ -225,7 +282,8 @@ impl TupleStore for ScyllaTupleStore {
             .map_err(|e| SentinelError::from_rows_error(e, "Failed to access rows"))?.next() {
             let tuple: RelationTuple = row
                 .map_err(|e| SentinelError::internal_error(format!("Row parsing error: {}", e)))?;
-            Ok(Some(tuple))
+            // TTL로 삭제되기 전의 만료된 튜플은 없는 것으로 처리
+            Ok(Some(tuple).filter(|tuple| !tuple.is_expired()))
         } else {
             Ok(None)
         }
@@ -238,7 +296,7 @@ impl TupleStore for ScyllaTupleStore {
         object_id: &str,
     ) -> SentinelResult<Vec<RelationTuple>> {
         let query = "
-            SELECT namespace, object_id, relation, user_type, user_id, created_at
+            SELECT namespace, object_id, relation, user_type, user_id, created_at, expires_at, caveat
             FROM sentinel.relation_tuples 
             WHERE namespace = ? AND object_id = ?
         ";
@@ -258,7 +316,9 @@ impl TupleStore for ScyllaTupleStore {
             .map_err(|e| SentinelError::from_rows_error(e, "Failed to access rows"))? {
             let tuple: RelationTuple = row
                 .map_err(|e| SentinelError::internal_error(format!("Row parsing error: {}", e)))?;
-            tuples.push(tuple);
+            if !tuple.is_expired() {
+                tuples.push(tuple);
+            }
         }
         
         Ok(tuples)
@@ -272,7 +332,7 @@ impl TupleStore for ScyllaTupleStore {
         relation: &str,
     ) -> SentinelResult<Vec<RelationTuple>> {
         let query = "
-            SELECT namespace, object_id, relation, user_type, user_id, created_at
+            SELECT namespace, object_id, relation, user_type, user_id, created_at, expires_at, caveat
             FROM sentinel.relation_tuples 
             WHERE namespace = ? AND object_id = ? AND relation = ?
         ";
@@ -292,7 +352,9 @@ impl TupleStore for ScyllaTupleStore {
             .map_err(|e| SentinelError::from_rows_error(e, "Failed to access rows"))? {
             let tuple: RelationTuple = row
                 .map_err(|e| SentinelError::internal_error(format!("Row parsing error: {}", e)))?;
-            tuples.push(tuple);
+            if !tuple.is_expired() {
+                tuples.push(tuple);
+            }
         }
         
         Ok(tuples)
@@ -300,18 +362,23 @@ impl TupleStore for ScyllaTupleStore {
     
     /// 사용자의 그룹 멤버십 조회 (최적화된 인덱스 테이블 사용)
     async fn find_user_memberships(&self, user_id: &str) -> SentinelResult<Vec<RelationTuple>> {
+        self.find_subject_memberships("user", user_id).await
+    }
+
+    /// 특정 주체가 저장된 모든 튜플 조회
+    async fn find_subject_memberships(&self, user_type: &str, user_id: &str) -> SentinelResult<Vec<RelationTuple>> {
         let query = "
-            SELECT user_id, user_type, namespace, object_id, relation, created_at
+            SELECT user_id, user_type, namespace, object_id, relation, created_at, expires_at, caveat
             FROM sentinel.user_memberships 
-            WHERE user_id = ? AND user_type = 'user'
+            WHERE user_id = ? AND user_type = ?
         ";
         
-        let values = (user_id,);
+        let values = (user_id, user_type);
         
         let result = self.session
             .query_unpaged(query, values)
             .await
-            .map_err(|e| SentinelError::from_scylla_error(e, "Failed to find user memberships"))?;
+            .map_err(|e| SentinelError::from_scylla_error(e, "Failed to find subject memberships"))?;
             
         let rows = result.into_rows_result()
             .map_err(|e| SentinelError::internal_error(format!("Query result error: {}", e)))?;
@@ -320,7 +387,7 @@ impl TupleStore for ScyllaTupleStore {
         for row in rows.rows()
             .map_err(|e| SentinelError::from_rows_error(e, "Failed to access rows"))? {
             // user_memberships 테이블의 컬럼 순서에 맞춰 RelationTuple 생성
-            let (user_id, user_type, namespace, object_id, relation, created_at): (String, String, String, String, String, CqlTimestamp) = row
+            let (user_id, user_type, namespace, object_id, relation, created_at, expires_at, caveat): SubjectMembershipRow = row
                 .map_err(|e| SentinelError::internal_error(format!("Row parsing error: {}", e)))?;
             
             let tuple = RelationTuple {
@@ -330,8 +397,12 @@ impl TupleStore for ScyllaTupleStore {
                 user_type,
                 user_id,
                 created_at,
+                expires_at,
+                caveat,
             };
-            tuples.push(tuple);
+            if !tuple.is_expired() {
+                tuples.push(tuple);
+            }
         }
         
         Ok(tuples)
@@ -345,7 +416,7 @@ impl TupleStore for ScyllaTupleStore {
         relation: &str,
     ) -> SentinelResult<Vec<RelationTuple>> {
         let query = "
-            SELECT namespace, object_id, relation, user_type, user_id, created_at
+            SELECT namespace, object_id, relation, user_type, user_id, created_at, expires_at, caveat
             FROM sentinel.relation_tuples 
             WHERE namespace = ? AND object_id = ? AND relation = ?
         ";
@@ -365,25 +436,304 @@ impl TupleStore for ScyllaTupleStore {
             .map_err(|e| SentinelError::from_rows_error(e, "Failed to access rows"))? {
             let tuple: RelationTuple = row
                 .map_err(|e| SentinelError::internal_error(format!("Row parsing error: {}", e)))?;
-            tuples.push(tuple);
+            if !tuple.is_expired() {
+                tuples.push(tuple);
+            }
         }
         
         Ok(tuples)
     }
     
-    /// 변경 이력 기록
+    /// 네임스페이스에 튜플이 저장된 관계 목록 (relation_index의 파티션 키 전체 조회)
+    /// 파티션 키 일부(namespace)로는 조회할 수 없으므로 모든 (namespace, relation) 파티션을 읽음
+    /// (네임스페이스 설정 변경 시에만 사용)
+    async fn stored_relations(&self, namespace: &str) -> SentinelResult<Vec<String>> {
+        let query = "SELECT DISTINCT namespace, relation FROM sentinel.relation_index";
+
+        let mut relations = std::collections::BTreeSet::new();
+        let mut paging_state = PagingState::start();
+        loop {
+            let statement = Statement::new(query).with_page_size(CHANGELOG_SCAN_PAGE_SIZE);
+            let (result, paging_response) = self.session
+                .query_single_page(statement, (), paging_state)
+                .await
+                .map_err(|e| SentinelError::from_scylla_error(e, "Failed to scan stored relations"))?;
+
+            let rows = result.into_rows_result()
+                .map_err(|e| SentinelError::internal_error(format!("Query result error: {}", e)))?;
+
+            for row in rows.rows::<(String, String)>()
+                .map_err(|e| SentinelError::from_rows_error(e, "Failed to access rows"))? {
+                let (row_namespace, relation) = row
+                    .map_err(|e| SentinelError::internal_error(format!("Row parsing error: {}", e)))?;
+                if row_namespace == namespace {
+                    relations.insert(relation);
+                }
+            }
+
+            match paging_response {
+                PagingStateResponse::HasMorePages { state } => paging_state = state,
+                PagingStateResponse::NoMorePages => break,
+            }
+        }
+
+        Ok(relations.into_iter().collect())
+    }
+
+    /// 필터에 맞는 튜플 한 페이지 조회 (Scylla paging state 기반)
+    /// 필터에 따라 relation_tuples, user_memberships, relation_index 중 하나를 선택 (QueryPlan)
+    async fn read_tuples(
+        &self,
+        filter: &RelationTupleFilter,
+        page_size: u32,
+        paging_state: Option<Vec<u8>>,
+    ) -> SentinelResult<TuplePage> {
+        let plan = QueryPlan::for_filter(filter)?;
+        let paging_state = paging_state
+            .map(PagingState::new_from_raw_bytes)
+            .unwrap_or_else(PagingState::start);
+
+        let statement = Statement::new(plan.to_cql()).with_page_size(page_size as i32);
+        let (result, paging_response) = self.session
+            .query_single_page(statement, plan.values(), paging_state)
+            .await
+            .map_err(|e| SentinelError::from_scylla_error(e, "Failed to read tuples"))?;
+
+        let rows = result.into_rows_result()
+            .map_err(|e| SentinelError::internal_error(format!("Query result error: {}", e)))?;
+
+        let mut tuples = Vec::new();
+        for row in rows.rows()
+            .map_err(|e| SentinelError::from_rows_error(e, "Failed to access rows"))? {
+            let tuple: RelationTuple = row
+                .map_err(|e| SentinelError::internal_error(format!("Row parsing error: {}", e)))?;
+            if !tuple.is_expired() {
+                tuples.push(tuple);
+            }
+        }
+
+        let paging_state = match paging_response {
+            PagingStateResponse::HasMorePages { state } => state.as_bytes_slice().map(|bytes| bytes.to_vec()),
+            PagingStateResponse::NoMorePages => None,
+        };
+
+        Ok(TuplePage { tuples, paging_state })
+    }
+
+    /// 테이블 전체 조회의 한 페이지 (Scylla paging state 기반)
+    async fn scan_table(
+        &self,
+        table: &'static TupleTable,
+        page_size: u32,
+        paging_state: Option<Vec<u8>>,
+    ) -> SentinelResult<TuplePage> {
+        let paging_state = paging_state
+            .map(PagingState::new_from_raw_bytes)
+            .unwrap_or_else(PagingState::start);
+
+        let statement = Statement::new(table.scan()).with_page_size(page_size as i32);
+        let (result, paging_response) = self.session
+            .query_single_page(statement, &[], paging_state)
+            .await
+            .map_err(|e| SentinelError::from_scylla_error(e, "Failed to scan tuple table"))?;
+
+        let rows = result.into_rows_result()
+            .map_err(|e| SentinelError::internal_error(format!("Query result error: {}", e)))?;
+
+        let mut tuples = Vec::new();
+        for row in rows.rows()
+            .map_err(|e| SentinelError::from_rows_error(e, "Failed to access rows"))? {
+            let tuple: RelationTuple = row
+                .map_err(|e| SentinelError::internal_error(format!("Row parsing error: {}", e)))?;
+            tuples.push(tuple);
+        }
+
+        let paging_state = match paging_response {
+            PagingStateResponse::HasMorePages { state } => state.as_bytes_slice().map(|bytes| bytes.to_vec()),
+            PagingStateResponse::NoMorePages => None,
+        };
+
+        Ok(TuplePage { tuples, paging_state })
+    }
+
+    /// 테이블의 기본 키로 행 하나 조회
+    async fn find_row(&self, table: &'static TupleTable, tuple: &RelationTuple) -> SentinelResult<Option<RelationTuple>> {
+        let result = self.session
+            .query_unpaged(table.select_one(), table.key_values(tuple))
+            .await
+            .map_err(|e| SentinelError::from_scylla_error(e, "Failed to look up tuple row"))?;
+
+        let rows = result.into_rows_result()
+            .map_err(|e| SentinelError::internal_error(format!("Query result error: {}", e)))?;
+
+        rows.maybe_first_row::<RelationTuple>()
+            .map_err(|e| SentinelError::internal_error(format!("Row parsing error: {}", e)))
+    }
+
+    async fn write_row(&self, table: &'static TupleTable, tuple: &RelationTuple) -> SentinelResult<()> {
+        let (query, values) = table.insert(tuple);
+        self.session
+            .query_unpaged(query, values)
+            .await
+            .map_err(|e| SentinelError::from_scylla_error(e, "Failed to write tuple row"))?;
+        Ok(())
+    }
+
+    async fn delete_row(&self, table: &'static TupleTable, tuple: &RelationTuple) -> SentinelResult<()> {
+        let (query, values) = table.delete(tuple);
+        self.session
+            .query_unpaged(query, values)
+            .await
+            .map_err(|e| SentinelError::from_scylla_error(e, "Failed to delete tuple row"))?;
+        Ok(())
+    }
+
+    /// 변경 이력 기록 (write_tuples의 batch와 같은 CQL)
     async fn record_change(&self, entry: &ChangelogEntry) -> SentinelResult<()> {
+        let (query, values) = changelog_mutation(entry);
+        self.session
+            .query_unpaged(query, values)
+            .await
+            .map_err(|e| SentinelError::from_scylla_error(e, "Failed to record changelog"))?;
+        Ok(())
+    }
+
+    /// 네임스페이스의 after_revision 이후 변경 이력 조회
+    /// after_revision이 속한 bucket부터 현재 bucket까지 파티션 단위로 범위 조회
+    async fn read_changes(&self, namespace: &str, after_revision: i64, limit: usize) -> SentinelResult<Vec<ChangelogEntry>> {
         let query = "
-            INSERT INTO sentinel.changelog 
-            (id, namespace, object_id, relation, user_type, user_id, operation, timestamp)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            SELECT namespace, bucket, revision, id, object_id, relation, user_type, user_id, operation, timestamp
+            FROM sentinel.changelog_by_namespace
+            WHERE namespace = ? AND bucket = ? AND revision > ?
+            LIMIT ?
         ";
-        
+
+        // 다른 노드의 시계가 약간 앞서 있어도 놓치지 않도록 다음 bucket까지 조회
+        let last_bucket = changelog_bucket(Utc::now().timestamp_micros()) + 1;
+        let mut entries = Vec::new();
+        let mut bucket = changelog_bucket(after_revision.saturating_add(1));
+
+        while bucket <= last_bucket && entries.len() < limit {
+            let remaining = (limit - entries.len()) as i32;
+            let result = self.session
+                .query_unpaged(query, (namespace, bucket, after_revision, remaining))
+                .await
+                .map_err(|e| SentinelError::from_scylla_error(e, "Failed to read changelog"))?;
+
+            let rows = result.into_rows_result()
+                .map_err(|e| SentinelError::internal_error(format!("Query result error: {}", e)))?;
+
+            for row in rows.rows()
+                .map_err(|e| SentinelError::from_rows_error(e, "Failed to access rows"))? {
+                let entry: ChangelogEntry = row
+                    .map_err(|e| SentinelError::internal_error(format!("Row parsing error: {}", e)))?;
+                entries.push(entry);
+            }
+            bucket += 1;
+        }
+
+        Ok(entries)
+    }
+
+    /// 네임스페이스마다 보관 기간 안의 시간 구간(bucket) 파티션을 하나씩 조회하며 주체의 행을 찾아 user_id 교체
+    /// user_type/user_id 조건은 파티션 안에서만 거르므로 (namespace 수 × 보관 기간의 bucket 수)번 조회
+    async fn pseudonymize_changes(
+        &self,
+        namespaces: &[String],
+        user_type: &str,
+        user_id: &str,
+        pseudonym: &str,
+    ) -> SentinelResult<u64> {
+        let select = "
+            SELECT namespace, bucket, revision, id
+            FROM sentinel.changelog_by_namespace
+            WHERE namespace = ? AND bucket = ? AND user_type = ? AND user_id = ?
+            ALLOW FILTERING
+        ";
+        let update = "
+            UPDATE sentinel.changelog_by_namespace SET user_id = ?
+            WHERE namespace = ? AND bucket = ? AND revision = ? AND id = ?
+        ";
+
+        // 다른 노드의 시계가 약간 앞서 있어도 놓치지 않도록 다음 bucket까지 조회
+        let now = Utc::now().timestamp_micros();
+        let first_bucket = changelog_bucket(now - CHANGELOG_RETENTION_MICROS);
+        let last_bucket = changelog_bucket(now) + 1;
+
+        let mut pseudonymized = 0;
+        for namespace in namespaces {
+            for bucket in first_bucket..=last_bucket {
+                let mut paging_state = PagingState::start();
+                loop {
+                    let statement = Statement::new(select).with_page_size(CHANGELOG_SCAN_PAGE_SIZE);
+                    let (result, paging_response) = self.session
+                        .query_single_page(statement, (namespace, bucket, user_type, user_id), paging_state)
+                        .await
+                        .map_err(|e| SentinelError::from_scylla_error(e, "Failed to scan changelog"))?;
+
+                    let rows = result.into_rows_result()
+                        .map_err(|e| SentinelError::internal_error(format!("Query result error: {}", e)))?;
+
+                    let mut keys = Vec::new();
+                    for row in rows.rows::<(String, i64, i64, Uuid)>()
+                        .map_err(|e| SentinelError::from_rows_error(e, "Failed to access rows"))? {
+                        keys.push(row
+                            .map_err(|e| SentinelError::internal_error(format!("Row parsing error: {}", e)))?);
+                    }
+
+                    let updates = keys.iter().map(|(namespace, bucket, revision, id)| {
+                        self.session.query_unpaged(update, (pseudonym, namespace, bucket, revision, id))
+                    });
+                    for result in join_all(updates).await {
+                        result.map_err(|e| SentinelError::from_scylla_error(e, "Failed to pseudonymize changelog"))?;
+                        pseudonymized += 1;
+                    }
+
+                    match paging_response {
+                        PagingStateResponse::HasMorePages { state } => paging_state = state,
+                        PagingStateResponse::NoMorePages => break,
+                    }
+                }
+            }
+        }
+
+        Ok(pseudonymized)
+    }
+
+    /// 사용자 삭제 영수증 저장 (JSON)
+    async fn record_erasure(&self, receipt: &ErasureReceipt) -> SentinelResult<()> {
+        let query = "INSERT INTO sentinel.erasure_receipts (receipt_id, receipt, erased_at) VALUES (?, ?, ?)";
+        let json = serde_json::to_string(receipt)
+            .map_err(|e| SentinelError::internal_error(format!("Receipt serialization error: {}", e)))?;
+
         self.session
-            .query_unpaged(query, entry)
+            .query_unpaged(query, (receipt.receipt_id, json, CqlTimestamp(receipt.erased_at.timestamp_millis())))
             .await
-            .map_err(|e| SentinelError::from_scylla_error(e, "Failed to record changelog"))?;
-            
+            .map_err(|e| SentinelError::from_scylla_error(e, "Failed to record erasure receipt"))?;
+
         Ok(())
     }
-}
\ No newline at end of file
+
+    /// 사용자 삭제 영수증 조회
+    async fn find_erasure(&self, receipt_id: Uuid) -> SentinelResult<Option<ErasureReceipt>> {
+        let query = "SELECT receipt FROM sentinel.erasure_receipts WHERE receipt_id = ?";
+
+        let result = self.session
+            .query_unpaged(query, (receipt_id,))
+            .await
+            .map_err(|e| SentinelError::from_scylla_error(e, "Failed to read erasure receipt"))?;
+
+        let rows = result.into_rows_result()
+            .map_err(|e| SentinelError::internal_error(format!("Query result error: {}", e)))?;
+
+        let json = match rows.maybe_first_row::<(String,)>()
+            .map_err(|e| SentinelError::internal_error(format!("Row parsing error: {}", e)))? {
+            Some((json,)) => json,
+            None => return Ok(None),
+        };
+
+        serde_json::from_str(&json)
+            .map(Some)
+            .map_err(|e| SentinelError::internal_error(format!("Receipt parsing error: {}", e)))
+    }
+}
@@ -0,0 +1,206 @@
+use chrono::Utc;
+use scylla::value::CqlValue;
+use uuid::Uuid;
+use crate::models::RelationTuple;
+
+/// 튜플을 저장하는 테이블과 기본 키 구성
+/// 네 테이블 모두 같은 다섯 컬럼을 기본 키로 사용하며 순서만 다름
+#[derive(Debug)]
+pub struct TupleTable {
+    /// 테이블 이름 (sentinel 키스페이스)
+    pub name: &'static str,
+    pub partition_key: &'static [&'static str],
+    pub clustering_key: &'static [&'static str],
+}
+
+/// 원본 테이블
+pub const RELATION_TUPLES: TupleTable = TupleTable {
+    name: "relation_tuples",
+    partition_key: &["namespace", "object_id"],
+    clustering_key: &["relation", "user_type", "user_id"],
+};
+
+/// 주체 기준 인덱스
+pub const USER_MEMBERSHIPS: TupleTable = TupleTable {
+    name: "user_memberships",
+    partition_key: &["user_id", "user_type"],
+    clustering_key: &["namespace", "object_id", "relation"],
+};
+
+/// 객체 기준 인덱스 (relation_tuples와 같은 키 구성)
+pub const OBJECT_PERMISSIONS: TupleTable = TupleTable {
+    name: "object_permissions",
+    partition_key: &["namespace", "object_id"],
+    clustering_key: &["relation", "user_type", "user_id"],
+};
+
+/// 네임스페이스-관계 기준 인덱스
+pub const RELATION_INDEX: TupleTable = TupleTable {
+    name: "relation_index",
+    partition_key: &["namespace", "relation"],
+    clustering_key: &["object_id", "user_type", "user_id"],
+};
+
+/// relation_tuples에서 파생되는 비정규화 인덱스 테이블
+pub const INDEX_TABLES: [&TupleTable; 3] = [&USER_MEMBERSHIPS, &OBJECT_PERMISSIONS, &RELATION_INDEX];
+
+/// 튜플 하나가 기록되는 모든 테이블
+pub const TUPLE_TABLES: [&TupleTable; 4] = [&RELATION_TUPLES, &USER_MEMBERSHIPS, &OBJECT_PERMISSIONS, &RELATION_INDEX];
+
+/// Create 선점 테이블 (튜플 키 하나가 파티션, 행은 CLAIM_TTL_SECS 뒤 삭제)
+/// 튜플을 저장하지 않으므로 TUPLE_TABLES에 포함하지 않음
+pub const TUPLE_CLAIMS: TupleTable = TupleTable {
+    name: "tuple_claims",
+    partition_key: &["namespace", "object_id", "relation", "user_type", "user_id"],
+    clustering_key: &[],
+};
+
+/// Create 선점 유지 시간 (초) - 선점한 쓰기의 batch가 끝나기에 충분한 시간
+/// 해제에 실패한 선점도 이 시간이 지나면 사라짐
+pub const CLAIM_TTL_SECS: i32 = 60;
+
+/// 튜플 조회 시 선택하는 컬럼 (RelationTuple 필드)
+pub const SELECT_COLUMNS: &str = "namespace, object_id, relation, user_type, user_id, created_at, expires_at, caveat";
+
+impl TupleTable {
+    /// 기본 키 컬럼 (파티션 키, 클러스터링 키 순)
+    pub fn key_columns(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.partition_key.iter().chain(self.clustering_key).copied()
+    }
+
+    /// 기본 키 값 (key_columns 순)
+    pub fn key_values(&self, tuple: &RelationTuple) -> Vec<CqlValue> {
+        self.key_columns()
+            .map(|column| CqlValue::Text(tuple_field(tuple, column).to_string()))
+            .collect()
+    }
+
+    /// 튜플 삽입 CQL과 바인딩 값
+    /// 만료 시간이 있으면 그때 행이 삭제되도록 TTL 지정 (없으면 TTL 0 = 만료 없음)
+    /// 만료 시간이나 조건이 없는 튜플은 이전에 기록된 값을 지우도록 null을 씀
+    pub fn insert(&self, tuple: &RelationTuple) -> (String, Vec<CqlValue>) {
+        let columns: Vec<&str> = self.key_columns().collect();
+        let placeholder = |present: bool| if present { "?" } else { "null" };
+        let query = format!(
+            "INSERT INTO sentinel.{} ({}, created_at, expires_at, caveat) VALUES ({}?, {}, {}) USING TTL ?",
+            self.name,
+            columns.join(", "),
+            "?, ".repeat(columns.len()),
+            placeholder(tuple.expires_at.is_some()),
+            placeholder(tuple.caveat.is_some()),
+        );
+        let mut values = self.key_values(tuple);
+        values.push(CqlValue::Timestamp(tuple.created_at));
+        values.extend(tuple.expires_at.map(CqlValue::Timestamp));
+        values.extend(tuple.caveat.clone().map(CqlValue::Text));
+        values.push(CqlValue::Int(ttl_seconds(tuple, Utc::now().timestamp_millis())));
+        (query, values)
+    }
+
+    /// 튜플 삭제 CQL과 바인딩 값
+    pub fn delete(&self, tuple: &RelationTuple) -> (String, Vec<CqlValue>) {
+        (format!("DELETE FROM sentinel.{} WHERE {}", self.name, self.key_condition()), self.key_values(tuple))
+    }
+
+    /// 같은 키의 선점이 없을 때만 선점하는 LWT CQL과 바인딩 값 (TUPLE_CLAIMS, 결과의 [applied]로 확인)
+    pub fn insert_claim(&self, tuple: &RelationTuple, claim_id: Uuid) -> (String, Vec<CqlValue>) {
+        let columns: Vec<&str> = self.key_columns().collect();
+        let query = format!(
+            "INSERT INTO sentinel.{} ({}, claim_id) VALUES ({}?) IF NOT EXISTS USING TTL ?",
+            self.name,
+            columns.join(", "),
+            "?, ".repeat(columns.len()),
+        );
+        let mut values = self.key_values(tuple);
+        values.push(CqlValue::Uuid(claim_id));
+        values.push(CqlValue::Int(CLAIM_TTL_SECS));
+        (query, values)
+    }
+
+    /// 자신의 선점일 때만 삭제하는 LWT CQL과 바인딩 값 (TUPLE_CLAIMS)
+    pub fn delete_claim(&self, tuple: &RelationTuple, claim_id: Uuid) -> (String, Vec<CqlValue>) {
+        let mut values = self.key_values(tuple);
+        values.push(CqlValue::Uuid(claim_id));
+        (format!("DELETE FROM sentinel.{} WHERE {} IF claim_id = ?", self.name, self.key_condition()), values)
+    }
+
+    /// 튜플 존재 여부 조회 CQL (key_values를 바인딩)
+    pub fn select_one(&self) -> String {
+        format!("SELECT {} FROM sentinel.{} WHERE {}", SELECT_COLUMNS, self.name, self.key_condition())
+    }
+
+    /// 테이블 전체 조회 CQL
+    pub fn scan(&self) -> String {
+        format!("SELECT {} FROM sentinel.{}", SELECT_COLUMNS, self.name)
+    }
+
+    fn key_condition(&self) -> String {
+        self.key_columns()
+            .map(|column| format!("{} = ?", column))
+            .collect::<Vec<_>>()
+            .join(" AND ")
+    }
+}
+
+/// 튜플 행의 TTL (초, 만료 시간까지 올림하며 이미 지났으면 1초)
+fn ttl_seconds(tuple: &RelationTuple, now_millis: i64) -> i32 {
+    match tuple.expires_at {
+        Some(expires_at) => ((expires_at.0 - now_millis).max(1) + 999).div_euclid(1000).min(i32::MAX as i64) as i32,
+        None => 0,
+    }
+}
+
+/// 컬럼 이름에 해당하는 튜플 필드
+pub fn tuple_field<'a>(tuple: &'a RelationTuple, column: &str) -> &'a str {
+    match column {
+        "namespace" => &tuple.namespace,
+        "object_id" => &tuple.object_id,
+        "relation" => &tuple.relation,
+        "user_type" => &tuple.user_type,
+        _ => &tuple.user_id,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scylla::value::CqlTimestamp;
+
+    #[test]
+    fn test_tuple_table_cql() {
+        let tuple = RelationTuple::new("documents", "doc1", "viewer", "user", "alice");
+
+        let (query, values) = USER_MEMBERSHIPS.insert(&tuple);
+        assert_eq!(
+            query,
+            "INSERT INTO sentinel.user_memberships (user_id, user_type, namespace, object_id, relation, created_at, expires_at, caveat) VALUES (?, ?, ?, ?, ?, ?, null, null) USING TTL ?",
+        );
+        assert_eq!(values[0], CqlValue::Text("alice".to_string()));
+        assert_eq!(values[5], CqlValue::Timestamp(tuple.created_at));
+        assert_eq!(values[6], CqlValue::Int(0));
+
+        // 만료 시간이 있으면 바인딩하고 만료 시간까지 초 단위로 올림한 TTL 지정
+        let expiring = tuple.clone().with_expiry(Some(CqlTimestamp(10_500)));
+        let (query, values) = RELATION_TUPLES.insert(&expiring);
+        assert!(query.ends_with("VALUES (?, ?, ?, ?, ?, ?, ?, null) USING TTL ?"));
+        assert_eq!(values[6], CqlValue::Timestamp(CqlTimestamp(10_500)));
+        assert_eq!(ttl_seconds(&expiring, 0), 11);
+        assert_eq!(ttl_seconds(&expiring, 20_000), 1);
+
+        let claim_id = Uuid::new_v4();
+        let (query, values) = TUPLE_CLAIMS.insert_claim(&tuple, claim_id);
+        assert_eq!(
+            query,
+            "INSERT INTO sentinel.tuple_claims (namespace, object_id, relation, user_type, user_id, claim_id) VALUES (?, ?, ?, ?, ?, ?) IF NOT EXISTS USING TTL ?",
+        );
+        assert_eq!(values[5], CqlValue::Uuid(claim_id));
+        assert_eq!(values[6], CqlValue::Int(CLAIM_TTL_SECS));
+
+        let (query, values) = RELATION_INDEX.delete(&tuple);
+        assert_eq!(
+            query,
+            "DELETE FROM sentinel.relation_index WHERE namespace = ? AND relation = ? AND object_id = ? AND user_type = ? AND user_id = ?",
+        );
+        assert_eq!(values[1], CqlValue::Text("viewer".to_string()));
+    }
+}
@@ -0,0 +1,360 @@
+use std::collections::BTreeSet;
+use futures::future::join_all;
+use tracing::warn;
+use uuid::Uuid;
+use crate::models::{Operation, RelationTuple, RelationTupleFilter, UpdateOutcome, UpdateResult};
+use crate::namespace_store::{NamespaceRegistry, MAX_WRITE_UPDATES};
+use crate::tuple_store::TupleStore;
+use crate::errors::{SentinelError, SentinelResult};
+
+/// 쓰기 요청을 현재 저장소 상태에 적용한 결과
+#[derive(Debug, Clone)]
+pub struct WritePlan {
+    /// 실제로 저장소를 바꾸는 변경 (Insert 또는 Delete)
+    pub mutations: Vec<(Operation, RelationTuple)>,
+    /// 업데이트별 결과 (요청과 같은 순서)
+    pub results: Vec<UpdateResult>,
+    /// Create로 추가하는 튜플 (apply_plan에서 반영 전에 선점)
+    pub creates: Vec<RelationTuple>,
+}
+
+/// 업데이트들을 현재 튜플과 비교해 실제 변경과 업데이트별 결과를 계산
+/// - Insert/Touch: 없으면 추가, 있으면 변경 없음 (created_at과 변경 이력을 새로 만들지 않음)
+///   만료 시간이나 조건만 다르면 created_at을 유지한 채 바꿈
+/// - Create: 없으면 추가, 있으면 쓰기 전체를 ConflictError로 거부
+///   (여기서는 읽은 시점의 상태만 확인하며, 동시에 같은 튜플을 Create하는 쓰기는 apply_plan에서 거부)
+/// - Delete: 있으면 삭제, 없으면 변경 없음
+pub async fn plan_updates(
+    tuple_store: &dyn TupleStore,
+    updates: &[(Operation, RelationTuple)],
+) -> SentinelResult<WritePlan> {
+    let existing = join_all(updates.iter().map(|(_, tuple)| tuple_store.find_direct_tuple(tuple))).await;
+
+    let mut plan = WritePlan {
+        mutations: Vec::new(),
+        results: Vec::with_capacity(updates.len()),
+        creates: Vec::new(),
+    };
+    for (index, ((operation, tuple), existing)) in updates.iter().zip(existing).enumerate() {
+        let outcome = match (operation, existing?) {
+            (Operation::Create, Some(_)) => {
+                return Err(SentinelError::conflict_error(format!(
+                    "Tuple {}:{}#{}@{}:{} in update {} already exists",
+                    tuple.namespace, tuple.object_id, tuple.relation, tuple.user_type, tuple.user_id, index,
+                )));
+            }
+            (Operation::Delete, Some(_)) => {
+                plan.mutations.push((Operation::Delete, tuple.clone()));
+                UpdateOutcome::Deleted
+            }
+            (Operation::Delete, None) => UpdateOutcome::NotFound,
+            (Operation::Create, None) => {
+                plan.mutations.push((Operation::Insert, tuple.clone()));
+                plan.creates.push(tuple.clone());
+                UpdateOutcome::Created
+            }
+            (_, Some(existing)) if existing.expires_at != tuple.expires_at || existing.caveat != tuple.caveat => {
+                plan.mutations.push((Operation::Insert, RelationTuple {
+                    created_at: existing.created_at,
+                    ..tuple.clone()
+                }));
+                UpdateOutcome::Updated
+            }
+            (_, Some(_)) => UpdateOutcome::Unchanged,
+            (_, None) => {
+                plan.mutations.push((Operation::Insert, tuple.clone()));
+                UpdateOutcome::Created
+            }
+        };
+        plan.results.push(UpdateResult {
+            operation: operation.clone(),
+            outcome,
+        });
+    }
+    Ok(plan)
+}
+
+/// 계획한 변경을 저장소에 반영
+/// Create 튜플은 먼저 선점 테이블에서 선점(claim_tuple)한 뒤 원본 튜플을 다시 읽어, 다른 쓰기가 선점 중이거나
+/// plan_updates 이후 같은 튜플을 만들었으면 ConflictError로 전체를 거부
+/// 튜플 테이블은 write_tuples의 batch로만 기록하므로 반영이 끝나기 전에는 Check에 보이지 않음
+/// 선점은 반영 결과와 상관없이 해제하며, 해제에 실패해도 CLAIM_TTL_SECS 뒤 사라짐
+pub async fn apply_plan(tuple_store: &dyn TupleStore, plan: &WritePlan) -> SentinelResult<()> {
+    let claim_id = Uuid::new_v4();
+    let mut claimed = Vec::with_capacity(plan.creates.len());
+    let mut result = Ok(());
+    for tuple in &plan.creates {
+        match tuple_store.claim_tuple(tuple, claim_id).await {
+            Ok(true) => claimed.push(tuple),
+            Ok(false) => {
+                result = Err(concurrent_create_error(tuple));
+                break;
+            }
+            Err(e) => {
+                result = Err(e);
+                break;
+            }
+        }
+    }
+    // 선점하기 전에 끝난 다른 쓰기가 같은 튜플을 만들었는지 확인
+    if result.is_ok() {
+        let existing = join_all(plan.creates.iter().map(|tuple| tuple_store.find_direct_tuple(tuple))).await;
+        for (tuple, existing) in plan.creates.iter().zip(existing) {
+            match existing {
+                Ok(None) => {}
+                Ok(Some(_)) => {
+                    result = Err(concurrent_create_error(tuple));
+                    break;
+                }
+                Err(e) => {
+                    result = Err(e);
+                    break;
+                }
+            }
+        }
+    }
+    if result.is_ok() {
+        result = tuple_store.write_tuples(&plan.mutations).await;
+    }
+
+    for tuple in claimed {
+        if let Err(e) = tuple_store.release_tuple(tuple, claim_id).await {
+            warn!("Failed to release claim on tuple {}: {}", tuple.to_string_representation(), e);
+        }
+    }
+    result
+}
+
+fn concurrent_create_error(tuple: &RelationTuple) -> SentinelError {
+    SentinelError::conflict_error(format!(
+        "Tuple {}:{}#{}@{}:{} was created by a concurrent write",
+        tuple.namespace, tuple.object_id, tuple.relation, tuple.user_type, tuple.user_id,
+    ))
+}
+
+/// 필터 삭제 결과
+#[derive(Debug, Clone)]
+pub struct DeleteOutcome {
+    /// 삭제된 튜플
+    pub deleted: Vec<RelationTuple>,
+    /// 필터에 맞는 튜플이 더 남아 있지 않은지
+    pub complete: bool,
+}
+
+/// 필터에 맞는 튜플을 최대 limit개 삭제 (없으면 모두)
+/// 조회한 페이지(최대 MAX_WRITE_UPDATES개)마다 하나의 batch로 삭제하므로 페이지 단위로 원자적이며,
+/// 중간에 실패해도 같은 필터로 다시 호출하면 남은 튜플부터 이어서 삭제됨
+pub async fn delete_matching(
+    tuple_store: &dyn TupleStore,
+    filter: &RelationTupleFilter,
+    limit: Option<usize>,
+) -> SentinelResult<DeleteOutcome> {
+    let mut deleted = Vec::new();
+    let mut paging_state = None;
+    let exhausted = loop {
+        let remaining = limit.map_or(MAX_WRITE_UPDATES, |limit| limit - deleted.len());
+        if remaining == 0 {
+            break false;
+        }
+
+        let page = tuple_store
+            .read_tuples(filter, remaining.min(MAX_WRITE_UPDATES) as u32, paging_state)
+            .await?;
+        let mutations: Vec<(Operation, RelationTuple)> = page.tuples.into_iter()
+            .map(|tuple| (Operation::Delete, tuple))
+            .collect();
+        tuple_store.write_tuples(&mutations).await?;
+        deleted.extend(mutations.into_iter().map(|(_, tuple)| tuple));
+
+        match page.paging_state {
+            Some(state) => paging_state = Some(state),
+            None => break true,
+        }
+    };
+
+    // limit에 걸려 멈췄으면 남은 튜플이 있는지 확인
+    let complete = exhausted || !tuple_store.has_matching_tuple(filter).await?;
+    Ok(DeleteOutcome { deleted, complete })
+}
+
+/// 객체 삭제 결과
+#[derive(Debug, Clone)]
+pub struct ObjectDeletion {
+    /// 객체가 리소스인 튜플 (namespace:object_id#*@*)
+    pub resource_tuples: Vec<RelationTuple>,
+    /// 객체가 주체인 튜플 (*@userset:namespace:object_id#*, *@namespace:object_id)
+    pub subject_tuples: Vec<RelationTuple>,
+}
+
+/// 객체와 관련된 모든 튜플 삭제 (cascade)
+/// 리소스 쪽 튜플을 먼저 지운 뒤, 객체를 userset 주체나 객체 주체(tuple_to_userset)로 참조하는 튜플을 지움
+/// userset 주체는 설정에 선언된 관계와 리소스 쪽 튜플에 실제로 쓰인 관계를 모두 확인
+/// 여러 batch에 걸쳐 삭제하므로 중간에 실패하면 다시 호출해 이어서 삭제
+pub async fn delete_object(
+    tuple_store: &dyn TupleStore,
+    namespaces: &NamespaceRegistry,
+    namespace: &str,
+    object_id: &str,
+) -> SentinelResult<ObjectDeletion> {
+    let resource_filter = RelationTupleFilter {
+        namespace: Some(namespace.to_string()),
+        object_id: Some(object_id.to_string()),
+        relation: None,
+        user_type: None,
+        user_id: None,
+    };
+    let resource_tuples = delete_matching(tuple_store, &resource_filter, None).await?.deleted;
+
+    let schema = namespaces.get(namespace).await?;
+    let mut relations: BTreeSet<&str> = schema.config.relation_names().into_iter().collect();
+    relations.extend(resource_tuples.iter().map(|tuple| tuple.relation.as_str()));
+
+    let subjects = relations.into_iter()
+        .map(|relation| ("userset".to_string(), format!("{}:{}#{}", namespace, object_id, relation)))
+        .chain([(namespace.to_string(), object_id.to_string())]);
+
+    let mut subject_tuples = Vec::new();
+    for (user_type, user_id) in subjects {
+        let subject_filter = RelationTupleFilter {
+            namespace: None,
+            object_id: None,
+            relation: None,
+            user_type: Some(user_type),
+            user_id: Some(user_id),
+        };
+        subject_tuples.extend(delete_matching(tuple_store, &subject_filter, None).await?.deleted);
+    }
+
+    Ok(ObjectDeletion {
+        resource_tuples,
+        subject_tuples,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use crate::test_utils::{parse_tuple, MemoryTupleStore, MemoryNamespaceStore};
+
+    #[tokio::test]
+    async fn test_plan_updates() {
+        let store = MemoryTupleStore::with_tuples(&["teams:backend#owner@user:alice"]);
+        let updates = vec![
+            (Operation::Touch, parse_tuple("teams:backend#owner@user:alice")),
+            (Operation::Insert, parse_tuple("teams:backend#member@user:bob")),
+            (Operation::Create, parse_tuple("teams:backend#member@user:carol")),
+            (Operation::Delete, parse_tuple("teams:backend#member@user:dave")),
+            (Operation::Delete, parse_tuple("teams:backend#owner@user:alice")),
+        ];
+
+        let plan = plan_updates(&store, &updates).await.unwrap();
+        let outcomes: Vec<UpdateOutcome> = plan.results.iter().map(|r| r.outcome).collect();
+        assert_eq!(outcomes, vec![
+            UpdateOutcome::Unchanged,
+            UpdateOutcome::Created,
+            UpdateOutcome::Created,
+            UpdateOutcome::NotFound,
+            UpdateOutcome::Deleted,
+        ]);
+        // 상태를 바꾸는 업데이트만 변경 이력에 남음
+        let users: Vec<&str> = plan.mutations.iter().map(|(_, t)| t.user_id.as_str()).collect();
+        assert_eq!(users, vec!["bob", "carol", "alice"]);
+
+        // 만료 시간만 바꾸면 created_at을 유지한 채 다시 기록
+        let expiry = Some(scylla::value::CqlTimestamp(i64::MAX));
+        let plan = plan_updates(&store, &[
+            (Operation::Touch, parse_tuple("teams:backend#owner@user:alice").with_expiry(expiry)),
+        ]).await.unwrap();
+        assert_eq!(plan.results[0].outcome, UpdateOutcome::Updated);
+        assert_eq!(plan.mutations[0].1.expires_at, expiry);
+
+        // 이미 있는 튜플을 Create하면 전체 거부
+        let result = plan_updates(&store, &[
+            (Operation::Create, parse_tuple("teams:backend#member@user:erin")),
+            (Operation::Create, parse_tuple("teams:backend#owner@user:alice")),
+        ]).await;
+        assert!(matches!(result, Err(SentinelError::ConflictError { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_apply_plan_concurrent_create() {
+        let store = MemoryTupleStore::new();
+        let create = |user_id: &str| (Operation::Create, parse_tuple(&format!("teams:backend#member@user:{}", user_id)));
+
+        // 두 쓰기가 같은 상태를 읽고 같은 튜플을 Create하면 먼저 반영한 쓰기만 성공
+        let first = plan_updates(&store, &[create("carol")]).await.unwrap();
+        let second = plan_updates(&store, &[create("dave"), create("carol")]).await.unwrap();
+        apply_plan(&store, &first).await.unwrap();
+        assert!(matches!(apply_plan(&store, &second).await, Err(SentinelError::ConflictError { .. })));
+
+        // 거부된 쓰기의 다른 튜플은 기록하지 않고 변경 이력도 남기지 않음
+        assert!(store.find_direct_tuple(&parse_tuple("teams:backend#member@user:dave")).await.unwrap().is_none());
+        assert_eq!(store.read_changes("teams", 0, 10).await.unwrap().len(), 1);
+
+        // 다른 쓰기가 선점 중인 튜플은 반영 전이어도 거부하고, 선점만으로는 튜플이 보이지 않음
+        let erin = parse_tuple("teams:backend#member@user:erin");
+        let other = Uuid::new_v4();
+        assert!(store.claim_tuple(&erin, other).await.unwrap());
+        assert!(store.find_direct_tuple(&erin).await.unwrap().is_none());
+        let plan = plan_updates(&store, &[create("erin")]).await.unwrap();
+        assert!(matches!(apply_plan(&store, &plan).await, Err(SentinelError::ConflictError { .. })));
+        store.release_tuple(&erin, other).await.unwrap();
+        apply_plan(&store, &plan).await.unwrap();
+        assert!(store.find_direct_tuple(&erin).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_delete_matching() {
+        let store = MemoryTupleStore::with_tuples(&[
+            "teams:backend#owner@user:alice",
+            "teams:backend#member@user:bob",
+            "teams:backend#member@user:carol",
+            "teams:frontend#member@user:bob",
+        ]);
+        let filter = RelationTupleFilter {
+            namespace: Some("teams".to_string()),
+            object_id: Some("backend".to_string()),
+            relation: None,
+            user_type: None,
+            user_id: None,
+        };
+
+        let outcome = delete_matching(&store, &filter, Some(2)).await.unwrap();
+        assert_eq!(outcome.deleted.len(), 2);
+        assert!(!outcome.complete);
+
+        let outcome = delete_matching(&store, &filter, None).await.unwrap();
+        assert_eq!(outcome.deleted.len(), 1);
+        assert!(outcome.complete);
+        assert!(!store.has_matching_tuple(&filter).await.unwrap());
+        // 다른 객체의 튜플과 변경 이력
+        assert!(store.find_direct_tuple(&parse_tuple("teams:frontend#member@user:bob")).await.unwrap().is_some());
+        assert_eq!(store.read_changes("teams", 0, 10).await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_delete_object() {
+        let store = MemoryTupleStore::with_tuples(&[
+            "teams:backend#owner@user:alice",
+            "teams:backend#member@user:bob",
+            "teams:backend#legacy@user:carol",
+            "documents:doc1#viewer@userset:teams:backend#member",
+            "documents:doc2#viewer@userset:teams:backend#legacy",
+            "documents:doc3#parent@teams:backend",
+            "documents:doc4#viewer@userset:teams:frontend#member",
+        ]);
+        let namespaces = NamespaceRegistry::new(Arc::new(MemoryNamespaceStore::default()));
+        namespaces.register_builtin().await.unwrap();
+
+        let deletion = delete_object(&store, &namespaces, "teams", "backend").await.unwrap();
+        assert_eq!(deletion.resource_tuples.len(), 3);
+        // 설정에 없는 관계(legacy)로 참조하는 userset 주체와 객체 주체도 삭제
+        let objects: Vec<&str> = deletion.subject_tuples.iter().map(|t| t.object_id.as_str()).collect();
+        assert_eq!(objects.len(), 3);
+        for object in ["doc1", "doc2", "doc3"] {
+            assert!(objects.contains(&object));
+        }
+        assert!(store.find_direct_tuple(&parse_tuple("documents:doc4#viewer@userset:teams:frontend#member")).await.unwrap().is_some());
+    }
+}
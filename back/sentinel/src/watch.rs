@@ -0,0 +1,247 @@
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::time::Duration;
+use actix_web::web::Bytes;
+use futures::stream::{self, Stream};
+use tracing::warn;
+use uuid::Uuid;
+use crate::models::{ChangelogEntry, WatchEvent, CHANGELOG_RETENTION_MICROS};
+use crate::tuple_store::TupleStore;
+use crate::zookie::Zookie;
+use crate::errors::{SentinelError, SentinelResult};
+
+/// changelog 폴링 간격
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// 변경이 없을 때 연결 유지용 주석을 보내는 간격 (폴링 횟수)
+const HEARTBEAT_POLLS: u32 = 15;
+/// 한 번의 폴링에서 읽는 최대 변경 수
+const POLL_LIMIT: usize = 500;
+/// 늦게 커밋되는 변경을 기다리는 시간 (마이크로초)
+/// revision은 각 노드의 시계로 쓰기 전에 발급되므로, 동시 요청이나 다른 노드, 시계 차이로
+/// 더 작은 revision의 변경이 나중에 커밋될 수 있음. 이 시간이 지난 revision만 커서를 넘김
+const SETTLE_WINDOW_MICROS: i64 = 10_000_000;
+
+/// 네임스페이스의 changelog를 revision 순으로 따라가며 새 변경을 전달하는 Watch 커서
+pub struct ChangeWatcher {
+    tuple_store: Arc<dyn TupleStore>,
+    namespace: String,
+    /// 이 revision까지의 변경은 모두 전달함 (SETTLE_WINDOW_MICROS가 지난 revision까지만 이동)
+    cursor: i64,
+    /// cursor 이후의 변경 중 이미 전달한 변경 (revision, id)
+    delivered: BTreeSet<(i64, Uuid)>,
+}
+
+impl ChangeWatcher {
+    /// start 시점 이후의 변경부터 전달하는 ChangeWatcher 생성
+    /// revision은 마이크로초 타임스탬프 기반이므로 zookie의 timestamp_micros를 그대로 사용
+    /// start가 changelog 보관 기간보다 오래되었는지는 validate_start로 먼저 확인
+    pub fn new(tuple_store: Arc<dyn TupleStore>, namespace: impl Into<String>, start: &Zookie) -> Self {
+        Self {
+            tuple_store,
+            namespace: namespace.into(),
+            cursor: start.timestamp_micros,
+            delivered: BTreeSet::new(),
+        }
+    }
+
+    /// 아직 전달하지 않은 변경 조회 (revision 순)
+    pub async fn poll(&mut self) -> SentinelResult<Vec<WatchEvent>> {
+        self.poll_at(chrono::Utc::now().timestamp_micros()).await
+    }
+
+    /// now_micros 시점 기준으로 아직 전달하지 않은 변경 조회
+    /// 커서 이후의 변경을 다시 읽어 이미 전달한 변경은 건너뛰므로, 늦게 커밋된 변경은
+    /// 더 큰 revision의 변경보다 나중에 전달될 수 있음
+    async fn poll_at(&mut self, now_micros: i64) -> SentinelResult<Vec<WatchEvent>> {
+        let mut after = self.cursor;
+        let mut events = Vec::new();
+        let mut full_page;
+        // 이미 전달한 변경만 있는 페이지는 건너뛰고 새 변경이 있는 페이지까지 읽음
+        loop {
+            let entries = self.tuple_store
+                .read_changes(&self.namespace, after, POLL_LIMIT)
+                .await?;
+            full_page = entries.len() == POLL_LIMIT;
+            for entry in &entries {
+                after = entry.revision;
+                if self.delivered.insert((entry.revision, entry.id)) {
+                    events.push(to_watch_event(entry)?);
+                }
+            }
+            if !full_page || !events.is_empty() {
+                break;
+            }
+        }
+
+        // 마지막 페이지가 차지 않았으면 now_micros까지 모두 읽었으므로 변경이 없어도 커서 이동
+        // (유휴 네임스페이스에서도 다음 폴링이 오래된 bucket을 다시 조회하지 않도록)
+        let read_until = if full_page { after } else { now_micros };
+        // 읽은 범위 안에서 늦은 커밋을 기다릴 시간이 지난 revision까지만 커서 이동
+        self.cursor = self.cursor.max(read_until.min(now_micros - SETTLE_WINDOW_MICROS));
+        self.delivered = self.delivered.split_off(&(self.cursor + 1, Uuid::nil()));
+        Ok(events)
+    }
+
+    /// 다시 연결할 때 사용할 zookie (이후 변경을 빠짐없이 받도록 커서 위치 사용)
+    fn resume_zookie(&self) -> SentinelResult<String> {
+        Zookie::from_timestamp(self.cursor).to_string()
+    }
+
+    /// server-sent events 스트림으로 변환
+    /// 변경마다 `change` 이벤트를 보내고, 오류가 나면 `error` 이벤트를 보낸 뒤 종료
+    pub fn into_sse_stream(self) -> impl Stream<Item = Result<Bytes, actix_web::Error>> {
+        stream::unfold(Some((self, 0u32)), |state| async move {
+            let (mut watcher, mut idle_polls) = state?;
+            loop {
+                match watcher.poll().await {
+                    Ok(events) if !events.is_empty() => {
+                        let resume = watcher.resume_zookie().unwrap_or_default();
+                        let body: String = events.iter().map(|event| format_sse_event(event, &resume)).collect();
+                        return Some((Ok(Bytes::from(body)), Some((watcher, 0))));
+                    }
+                    Ok(_) => {
+                        idle_polls += 1;
+                        if idle_polls >= HEARTBEAT_POLLS {
+                            return Some((Ok(Bytes::from_static(b": keep-alive\n\n")), Some((watcher, 0))));
+                        }
+                    }
+                    Err(e) => {
+                        warn!("Watch stream for namespace {} failed: {}", watcher.namespace, e);
+                        let data = serde_json::json!({ "message": e.to_string() });
+                        let body = format!("event: error\ndata: {}\n\n", data);
+                        return Some((Ok(Bytes::from(body)), None));
+                    }
+                }
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        })
+    }
+}
+
+/// Watch 시작 시점 확인
+/// changelog 보관 기간보다 오래된 시점은 이미 삭제된 변경을 빠뜨리고, 첫 폴링이 그 사이의 모든 bucket을 조회하므로 거부
+pub fn validate_start(start: &Zookie, now_micros: i64) -> SentinelResult<()> {
+    if start.timestamp_micros < now_micros - CHANGELOG_RETENTION_MICROS {
+        return Err(SentinelError::validation_error(
+            "Watch zookie is older than the changelog retention (7 days); resync with Read and watch from now",
+        ));
+    }
+    Ok(())
+}
+
+/// changelog 항목을 Watch 이벤트로 변환
+fn to_watch_event(entry: &ChangelogEntry) -> SentinelResult<WatchEvent> {
+    Ok(WatchEvent {
+        operation: entry.to_operation(),
+        tuple: entry.to_tuple().to_api_tuple(),
+        revision: entry.revision,
+        zookie: Zookie::from_timestamp(entry.revision).to_string()?,
+    })
+}
+
+/// SSE 형식으로 직렬화 (id는 재연결 시 사용할 zookie)
+fn format_sse_event(event: &WatchEvent, resume: &str) -> String {
+    let data = serde_json::to_string(event).unwrap_or_default();
+    format!("id: {}\nevent: change\ndata: {}\n\n", resume, data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Operation, CHANGELOG_BUCKET_MICROS, changelog_bucket, next_revision};
+    use crate::test_utils::{parse_tuple, MemoryTupleStore};
+
+    fn change(tuple: &str, operation: Operation, revision: i64) -> ChangelogEntry {
+        ChangelogEntry::with_revision(&parse_tuple(tuple), &operation, revision)
+    }
+
+    #[tokio::test]
+    async fn test_watch_poll() {
+        let store = Arc::new(MemoryTupleStore::new());
+        // 폴링할 때마다 커서가 현재 시각 기준으로 이동하므로 revision도 현재 시각 기준으로 발급
+        let base = chrono::Utc::now().timestamp_micros();
+        store.record_change(&change("documents:doc1#viewer@user:old", Operation::Insert, base + 1_000)).await.unwrap();
+        store.record_change(&change("documents:doc1#viewer@user:alice", Operation::Insert, base + 2_000)).await.unwrap();
+        store.record_change(&change("teams:backend#member@user:bob", Operation::Insert, base + 2_001)).await.unwrap();
+
+        let mut watcher = ChangeWatcher::new(store.clone(), "documents", &Zookie::from_timestamp(base + 1_000));
+        let events = watcher.poll().await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].tuple.user_id, "alice");
+        assert!(watcher.poll().await.unwrap().is_empty());
+
+        // 이후 변경은 revision 순으로 한 번씩만 전달
+        store.record_change(&change("documents:doc1#viewer@user:alice", Operation::Delete, base + 3_001)).await.unwrap();
+        store.record_change(&change("documents:doc1#viewer@user:carol", Operation::Insert, base + 3_000)).await.unwrap();
+        let events = watcher.poll().await.unwrap();
+        let changes: Vec<(&str, bool)> = events.iter()
+            .map(|e| (e.tuple.user_id.as_str(), matches!(e.operation, Operation::Delete)))
+            .collect();
+        assert_eq!(changes, vec![("carol", false), ("alice", true)]);
+        assert!(watcher.poll().await.unwrap().is_empty());
+
+        // 이벤트의 zookie로 재연결하면 그 이후 변경만 전달
+        let resume = Zookie::from_string(&events[0].zookie).unwrap();
+        let mut watcher = ChangeWatcher::new(store, "documents", &resume);
+        let events = watcher.poll().await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].revision, base + 3_001);
+        assert!(format_sse_event(&events[0], &watcher.resume_zookie().unwrap()).starts_with("id: "));
+    }
+
+    #[tokio::test]
+    async fn test_watch_late_commit() {
+        let store = Arc::new(MemoryTupleStore::new());
+        let mut watcher = ChangeWatcher::new(store.clone(), "documents", &Zookie::from_timestamp(1_000));
+
+        store.record_change(&change("documents:doc1#viewer@user:alice", Operation::Insert, 3_000)).await.unwrap();
+        let events = watcher.poll_at(3_500).await.unwrap();
+        assert_eq!(events.len(), 1);
+
+        // 더 작은 revision의 변경이 폴링 사이에 늦게 커밋되어도 전달
+        store.record_change(&change("documents:doc1#viewer@user:bob", Operation::Insert, 2_500)).await.unwrap();
+        let events = watcher.poll_at(4_000).await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].tuple.user_id, "bob");
+        assert!(watcher.poll_at(4_500).await.unwrap().is_empty());
+
+        // 재연결용 zookie는 늦은 커밋을 기다리는 동안 커서를 넘기지 않음
+        assert_eq!(Zookie::from_string(&watcher.resume_zookie().unwrap()).unwrap().timestamp_micros, 1_000);
+        assert!(watcher.poll_at(3_000 + SETTLE_WINDOW_MICROS).await.unwrap().is_empty());
+        assert_eq!(watcher.cursor, 3_000);
+        assert!(watcher.delivered.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_watch_idle_cursor() {
+        let store = Arc::new(MemoryTupleStore::new());
+        let mut watcher = ChangeWatcher::new(store.clone(), "documents", &Zookie::from_timestamp(1_000));
+
+        // 변경이 없어도 늦은 커밋을 기다리는 구간 앞까지 커서와 재연결 zookie가 이동
+        let now = 30 * CHANGELOG_BUCKET_MICROS;
+        assert!(watcher.poll_at(now).await.unwrap().is_empty());
+        assert_eq!(watcher.cursor, now - SETTLE_WINDOW_MICROS);
+        assert_eq!(Zookie::from_string(&watcher.resume_zookie().unwrap()).unwrap().timestamp_micros, now - SETTLE_WINDOW_MICROS);
+
+        // 구간 안에 늦게 커밋된 변경은 그대로 전달
+        store.record_change(&change("documents:doc1#viewer@user:alice", Operation::Insert, now - 1_000)).await.unwrap();
+        let events = watcher.poll_at(now + 1_000).await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(watcher.cursor, now + 1_000 - SETTLE_WINDOW_MICROS);
+    }
+
+    #[test]
+    fn test_validate_start() {
+        let now = 30 * CHANGELOG_BUCKET_MICROS;
+        assert!(validate_start(&Zookie::from_timestamp(now - CHANGELOG_RETENTION_MICROS), now).is_ok());
+        assert!(validate_start(&Zookie::from_timestamp(now - CHANGELOG_RETENTION_MICROS - 1), now).is_err());
+    }
+
+    #[test]
+    fn test_revision_order() {
+        let revisions: Vec<i64> = (0..1000).map(|_| next_revision()).collect();
+        assert!(revisions.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(changelog_bucket(CHANGELOG_BUCKET_MICROS - 1), 0);
+        assert_eq!(changelog_bucket(CHANGELOG_BUCKET_MICROS), 1);
+    }
+}
@@ -96,7 +96,7 @@ impl Zookie {
     /// DateTime으로 변환
     pub fn to_datetime(&self) -> DateTime<Utc> {
         DateTime::from_timestamp_micros(self.timestamp_micros)
-            .unwrap_or_else(|| Utc::now())
+            .unwrap_or_else(Utc::now)
     }
 }
 
@@ -271,56 +271,7 @@ impl<C: Cache> ZookieManager<C> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
-    use std::sync::Mutex;
-    
-    // 테스트용 간단한 캐시 구현
-    struct MockCache {
-        data: Arc<Mutex<HashMap<String, (String, i64)>>>,
-    }
-    
-    impl MockCache {
-        fn new() -> Self {
-            Self {
-                data: Arc::new(Mutex::new(HashMap::new())),
-            }
-        }
-    }
-    
-    #[async_trait::async_trait]
-    impl Cache for MockCache {
-        async fn get(&self, key: &str) -> SentinelResult<Option<String>> {
-            let data = self.data.lock().unwrap();
-            if let Some((value, expiry)) = data.get(key) {
-                if Utc::now().timestamp() < *expiry {
-                    Ok(Some(value.clone()))
-                } else {
-                    Ok(None)
-                }
-            } else {
-                Ok(None)
-            }
-        }
-        
-        async fn set(&self, key: &str, value: &str, ttl_seconds: u64) -> SentinelResult<()> {
-            let expiry = Utc::now().timestamp() + ttl_seconds as i64;
-            let mut data = self.data.lock().unwrap();
-            data.insert(key.to_string(), (value.to_string(), expiry));
-            Ok(())
-        }
-        
-        async fn delete(&self, _key: &str) -> SentinelResult<()> {
-            Ok(())
-        }
-        
-        async fn delete_pattern(&self, _pattern: &str) -> SentinelResult<()> {
-            Ok(())
-        }
-        
-        async fn ping(&self) -> SentinelResult<()> {
-            Ok(())
-        }
-    }
+    use crate::test_utils::MockCache;
     
     #[tokio::test]
     async fn test_zookie_serialization() {
@@ -7,11 +7,31 @@ import io.ktor.http.*
 import io.ktor.server.application.*
 import io.ktor.server.response.*
 import io.ktor.server.routing.*
+import kotlinx.coroutines.delay
+import kotlinx.coroutines.isActive
+import kotlinx.coroutines.launch
+
+private const val SENTINEL_CLEANUP_INTERVAL_MILLIS = 60_000L
 
 fun Application.configureRouting() {
     val teamService = TeamService()
     val teamController = TeamController(teamService)
     
+    // 팀 삭제 중 Sentinel 권한 정리에 실패한 팀을 주기적으로 다시 정리
+    launch {
+        while (isActive) {
+            delay(SENTINEL_CLEANUP_INTERVAL_MILLIS)
+            try {
+                val remaining = teamService.retrySentinelCleanups()
+                if (remaining > 0) {
+                    println("⚠️ Sentinel 권한 정리 대기 중인 삭제된 팀: $remaining")
+                }
+            } catch (e: Exception) {
+                println("⚠️ Sentinel 권한 정리 재시도 실패: ${e.message}")
+            }
+        }
+    }
+    
     routing {
         // 헬스체크 엔드포인트
         get("/health") {
@@ -10,6 +10,7 @@ import io.ktor.serialization.kotlinx.json.*
 import kotlinx.serialization.Serializable
 import kotlinx.serialization.json.Json
 import kotlinx.datetime.Clock
+import kotlinx.coroutines.delay
 
 @Serializable
 data class SentinelTuple(
@@ -74,6 +75,11 @@ data class SentinelBatchCheckResponse(
 )
 
 class SentinelClient(private val baseUrl: String) {
+    companion object {
+        private const val REMOVE_ALL_MAX_ATTEMPTS = 3
+        private const val REMOVE_ALL_RETRY_DELAY_MILLIS = 500L
+    }
+    
     private val client = HttpClient(CIO) {
         install(ContentNegotiation) {
             json(Json {
@@ -273,60 +279,35 @@ class SentinelClient(private val baseUrl: String) {
     }
     
     /**
-     * 팀 삭제 시 모든 관련 권한 제거 (owner, member 등 모든 관계)
+     * 팀 삭제 시 모든 관련 권한 제거 (팀의 모든 관계와 teams:{teamId}#member 등 팀을 주체로 쓰는 권한)
+     * Sentinel은 튜플을 여러 batch로 나눠 삭제하므로 중간에 실패하면 일부 튜플만 삭제된 채 남음.
+     * 같은 요청을 다시 보내면 남은 튜플부터 삭제하므로 서버 오류나 연결 실패는 최대 3번까지 다시 시도함.
+     * false면 권한이 남아 있을 수 있으므로 호출하는 쪽에서 나중에 다시 정리해야 함.
      */
-    suspend fun removeAllTeamPermissions(teamId: String, userIds: List<Int>): Boolean {
-        println("🔥 SentinelClient.removeAllTeamPermissions 호출: teamId=$teamId, userIds=$userIds")
-        if (userIds.isEmpty()) return true
+    suspend fun removeAllTeamPermissions(teamId: String): Boolean {
+        println("🔥 SentinelClient.removeAllTeamPermissions 호출: teamId=$teamId")
         
-        return try {
-            val updates = mutableListOf<SentinelTupleUpdate>()
-            
-            // 각 사용자에 대해 owner, member 권한 모두 삭제
-            for (userId in userIds) {
-                // owner 권한 삭제
-                updates.add(SentinelTupleUpdate(
-                    operation = "Delete",
-                    tuple = SentinelTuple(
-                        namespace = "teams",
-                        object_id = teamId,
-                        relation = "owner",
-                        user_type = "user",
-                        user_id = userId.toString(),
-                        created_at = Clock.System.now().toString()
-                    )
-                ))
-                
-                // member 권한 삭제 (혹시 있을 수 있으므로)
-                updates.add(SentinelTupleUpdate(
-                    operation = "Delete",
-                    tuple = SentinelTuple(
-                        namespace = "teams",
-                        object_id = teamId,
-                        relation = "member",
-                        user_type = "user",
-                        user_id = userId.toString(),
-                        created_at = Clock.System.now().toString()
-                    )
-                ))
-            }
-            
-            val request = SentinelWriteRequest(updates = updates)
-            
-            val response = client.post("$baseUrl/api/v1/write") {
-                contentType(ContentType.Application.Json)
-                setBody(request)
+        repeat(REMOVE_ALL_MAX_ATTEMPTS) { attempt ->
+            if (attempt > 0) {
+                delay(REMOVE_ALL_RETRY_DELAY_MILLIS * attempt)
             }
-            
-            val success = response.status.isSuccess()
-            if (!success) {
-                println("Sentinel removeAllTeamPermissions 실패: ${response.status} - teamId: $teamId, userIds: $userIds")
+            try {
+                // 팀 객체와 팀을 참조하는 튜플을 한 번에 삭제
+                val response = client.delete("$baseUrl/api/v1/objects/teams/$teamId")
+                
+                if (response.status.isSuccess()) {
+                    return true
+                }
+                println("Sentinel removeAllTeamPermissions 실패 (${attempt + 1}/$REMOVE_ALL_MAX_ATTEMPTS): ${response.status} - teamId: $teamId")
+                // 요청 자체가 잘못된 경우는 다시 보내도 같은 결과
+                if (response.status.value < 500) {
+                    return false
+                }
+            } catch (e: Exception) {
+                println("Sentinel removeAllTeamPermissions 호출 실패 (${attempt + 1}/$REMOVE_ALL_MAX_ATTEMPTS): ${e.message} - teamId: $teamId")
             }
-            success
-        } catch (e: Exception) {
-            println("Sentinel removeAllTeamPermissions 호출 실패: ${e.message} - teamId: $teamId, userIds: $userIds")
-            false
         }
+        return false
     }
     
     /**
@@ -12,6 +12,7 @@ import kotlinx.datetime.Instant
 import org.bson.types.ObjectId
 import com.mongodb.client.model.Filters
 import com.mongodb.client.model.Updates
+import com.mongodb.client.model.UpdateOptions
 import org.bson.Document
 import java.time.ZoneOffset
 import java.util.*
@@ -19,6 +20,8 @@ import java.util.*
 class TeamService {
     private val database = DatabaseConfig.getDatabase()
     private val teamsCollection: MongoCollection<Document> = database.getCollection("teams")
+    // Sentinel 권한 정리에 실패한 삭제된 팀 (_id: teamId)
+    private val sentinelCleanupsCollection: MongoCollection<Document> = database.getCollection("sentinelCleanups")
     private val sentinelClient = SentinelClient(
         System.getenv("SENTINEL_URL") ?: "http://localhost:15004"
     )
@@ -126,10 +129,12 @@ class TeamService {
         if (success && team != null) {
             val userIds = team.members.map { it.userId }
             println("🔥 팀 삭제 - Sentinel 권한 제거 시작: teamId=$teamId, userIds=$userIds")
-            val sentinelSuccess = sentinelClient.removeAllTeamPermissions(teamId, userIds)
+            val sentinelSuccess = sentinelClient.removeAllTeamPermissions(teamId)
             println("🔥 팀 삭제 - Sentinel 권한 제거 결과: $sentinelSuccess")
             if (!sentinelSuccess) {
                 println("⚠️ 팀 삭제는 성공했지만 Sentinel 권한 동기화 실패 - teamId: $teamId, memberIds: $userIds")
+                // 일부 튜플만 삭제되었을 수 있으므로 정리 대상으로 기록하고 retrySentinelCleanups에서 다시 삭제
+                markSentinelCleanup(teamId)
             }
         } else {
             println("🔥 팀 삭제 - Sentinel 호출 안됨: success=$success, team=$team")
@@ -138,6 +143,49 @@ class TeamService {
         return success
     }
     
+    /**
+     * 삭제된 팀의 Sentinel 권한 정리 재시도 (성공한 팀은 정리 대상에서 제거)
+     * @return 아직 정리하지 못한 팀 수
+     */
+    suspend fun retrySentinelCleanups(): Int {
+        val pending = sentinelCleanupsCollection.find().toList()
+        var remaining = 0
+        for (doc in pending) {
+            val teamId = doc.getString("_id")
+            if (sentinelClient.removeAllTeamPermissions(teamId)) {
+                sentinelCleanupsCollection.deleteOne(Filters.eq("_id", teamId))
+                println("🔥 삭제된 팀의 Sentinel 권한 정리 완료 - teamId: $teamId")
+            } else {
+                sentinelCleanupsCollection.updateOne(
+                    Filters.eq("_id", teamId),
+                    Updates.combine(
+                        Updates.inc("attempts", 1),
+                        Updates.set("updatedAt", Date.from(Clock.System.now().toJavaInstant()))
+                    )
+                )
+                remaining++
+            }
+        }
+        return remaining
+    }
+    
+    private suspend fun markSentinelCleanup(teamId: String) {
+        val nowDate = Date.from(Clock.System.now().toJavaInstant())
+        try {
+            sentinelCleanupsCollection.updateOne(
+                Filters.eq("_id", teamId),
+                Updates.combine(
+                    Updates.setOnInsert("createdAt", nowDate),
+                    Updates.inc("attempts", 1),
+                    Updates.set("updatedAt", nowDate)
+                ),
+                UpdateOptions().upsert(true)
+            )
+        } catch (e: Exception) {
+            println("⚠️ Sentinel 권한 정리 대상 기록 실패 - teamId: $teamId, error: ${e.message}")
+        }
+    }
+    
     suspend fun addMember(teamId: String, targetUserId: Int, requesterId: Int): Boolean {
         val objectId = try {
             ObjectId(teamId)